@@ -0,0 +1,66 @@
+use colored::Colorize;
+use std::process;
+use vnv::{
+    parsing::{self, config, Scope, ValueType},
+    util::secrets,
+};
+
+use crate::commands;
+
+#[derive(Debug)]
+pub struct Options {
+    pub config: config::Options,
+}
+
+/// Returns the string values of the key since tokens are always strings
+fn string_values(value: &ValueType) -> Vec<&str> {
+    match value {
+        ValueType::String(v) => vec![v.as_str()],
+        ValueType::StringArray(v) => v.iter().map(|s| s.as_str()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Flags public keys whose values look like secrets
+pub fn default(options: Options) {
+    println!("Auditing '{}'...", commands::source_name(&options.config.src));
+
+    let content = commands::read_source(&options.config.src);
+
+    let result = parsing::parse(&content);
+
+    let mut findings = 0;
+
+    for key in &result.keys {
+        if key.scope != Scope::Public {
+            continue;
+        }
+
+        let formats: Vec<&str> = string_values(&key.value)
+            .into_iter()
+            .filter_map(secrets::token_format)
+            .collect();
+
+        if let Some(format) = formats.first() {
+            findings += 1;
+            println!(
+                "{} {} looks like {format} but is marked @public ({}:{})",
+                key.key,
+                "!".yellow().bold(),
+                commands::source_name(&options.config.src),
+                key.position.line
+            );
+        }
+    }
+
+    if findings > 0 {
+        println!(
+            "{} {findings} public {}. Remove @public to keep them private.",
+            "ERROR:".bold().red(),
+            if findings == 1 { "key looks like a secret" } else { "keys look like secrets" }
+        );
+        process::exit(1);
+    }
+
+    println!("No public secrets found.");
+}
@@ -1,6 +1,11 @@
-use std::fs;
+use colored::Colorize;
+use std::{fs, path::Path, process};
 
-use vnv::{decorators::DecoratorValue, parsing::{self, config, Environment}};
+use vnv::diagnostics::{mask, Format};
+use vnv::parsing::config::{self, Target, TargetFormat};
+use vnv::parsing::{self, units::Unit, Environment, Key, ValueType};
+use vnv::parsing::Scope;
+use vnv::util::{closest_match, files};
 
 use crate::commands::{self, check};
 
@@ -8,55 +13,399 @@ use crate::commands::{self, check};
 pub struct Options {
     pub config: config::Options,
     pub environment: Environment,
+    /// Compares the existing output to the build instead of writing it
+    pub check: bool,
+    /// Name of the only target to build, every target is built if not provided
+    pub target: Option<String>,
+    /// Writes the build to stdout, everything else goes to stderr
+    pub stdout: bool,
+}
+
+/// Returns the targets to build, exits if the requested target doesn't exist
+fn select(options: &Options) -> Vec<Target> {
+    let targets = options.config.build.targets();
+
+    let name = match &options.target {
+        Some(name) => name,
+        None => return targets,
+    };
+
+    match targets.iter().find(|t| &t.name == name) {
+        Some(target) => vec![target.to_owned()],
+        None => {
+            let names: Vec<String> = targets.iter().map(|t| t.name.to_owned()).collect();
+
+            let suggestion = match closest_match(name, &names) {
+                Some(suggestion) => format!(" Did you mean '{suggestion}'?"),
+                None => format!(" Expected one of: {}.", names.join(", ")),
+            };
+
+            eprintln!(
+                "{} There is no build target named '{name}'.{suggestion}",
+                "ERROR:".bold().red()
+            );
+            process::exit(1);
+        }
+    }
 }
 
 pub fn default(options: Options) {
-    let file_content = fs::read(&options.config.src).unwrap();
+    let content = commands::read_source(&options.config.src);
 
-    let content = String::from_utf8(file_content).unwrap();
+    let targets = select(&options);
 
-    commands::check(check::Options {
-        config: options.config.to_owned(),
-        environment: options.environment.to_owned()
-    });
+    if options.stdout && targets.len() > 1 {
+        eprintln!(
+            "{} --stdout can only write one build target. Pick one with --target.",
+            "ERROR:".bold().red()
+        );
+        process::exit(1);
+    }
 
-    let result = parsing::parse(&content);
+    if options.check {
+        let mut up_to_date = true;
 
-    let mut file = String::new();
+        for target in &targets {
+            up_to_date &= verify(&options, target, &content);
+        }
+
+        if !up_to_date {
+            process::exit(1);
+        }
 
-    if !options.config.build.minify {
-        file.push_str(&format!(
-            "# This file was generated from '{}' by vnv.\n\n",
-            options.config.src
-        ))
+        return;
     }
 
-    for key in result.keys {
-        let environment_does_not_match = key.environment != options.environment;
-        let key_environment_not_all = key.environment != Environment::All;
+    // Each environment that is built is checked once
+    let mut checked: Vec<Environment> = Vec::new();
 
-        if environment_does_not_match && key_environment_not_all {
-            continue; // skip this key
+    for target in &targets {
+        let environment = target.environment_or(&options.environment);
+
+        if checked.contains(&environment) {
+            continue;
+        }
+
+        commands::check(check::Options {
+            config: options.config.to_owned(),
+            environment: environment.to_owned(),
+            format: Format::Terminal,
+            content: Some(content.to_owned()),
+            stderr: options.stdout,
+        });
+
+        checked.push(environment);
+    }
+
+    // The header can't be written to JSON so only .env files are protected
+    let foreign: Vec<&Target> = targets
+        .iter()
+        .filter(|t| t.format == TargetFormat::Dotenv && !files::can_overwrite(Path::new(&t.output)))
+        .collect();
+
+    if options.config.build.protect && !options.stdout && !foreign.is_empty() {
+        for target in foreign {
+            eprintln!(
+                "{} '{}' wasn't generated by vnv so it won't be overwritten. Move it or disable build.protect.",
+                "ERROR:".bold().red(),
+                target.output
+            );
         }
+        process::exit(1);
+    }
+
+    for target in &targets {
+        let environment = target.environment_or(&options.environment);
+
+        let file = generate(&options.config, target, &environment, &content);
+
+        if options.stdout {
+            print!("{file}");
+            continue;
+        }
+
+        // Builds with private keys are only readable by the owner
+        let private = parsing::parse(&content)
+            .resolve(&environment)
+            .iter()
+            .any(|k| k.scope == Scope::Private && target.includes(k));
 
-        if !options.config.build.minify {
-            for constraint in key.decorators {
-                let constraint_str = match constraint.value {
-                    DecoratorValue::String(v) => format!("@{}(\"{}\")", constraint.key, v),
-                    DecoratorValue::Integer(v) => format!("@{}({})", constraint.key, v),
-                    DecoratorValue::None => format!("@{}", constraint.key),
-                };
-                file.push_str(&format!("# {constraint_str}\n"));
+        if let Err(err) = files::write_atomic(Path::new(&target.output), &file, private) {
+            eprintln!(
+                "{} Couldn't write '{}': {err}",
+                "ERROR:".bold().red(),
+                target.output
+            );
+            process::exit(1);
+        }
+
+        if target.name == config::DEFAULT_TARGET {
+            println!("Completed build wrote output to {}.", target.output)
+        } else {
+            println!(
+                "Completed build of '{}' wrote output to {}.",
+                target.name, target.output
+            )
+        }
+    }
+}
+
+/// Builds the contents of the target from the source content
+pub fn generate(
+    config: &config::Options,
+    target: &Target,
+    environment: &Environment,
+    content: &str,
+) -> String {
+    let result = parsing::parse(content);
+
+    let minify = target.minify.unwrap_or(config.build.minify);
+
+    let keys: Vec<Key> = result
+        .resolve(environment)
+        .into_iter()
+        .filter(|key| target.includes(key))
+        .collect();
+
+    let value = |key: &Key| {
+        if config.build.normalize {
+            normalize(key)
+        } else {
+            key.value.to_owned()
+        }
+    };
+
+    if target.format == TargetFormat::Json {
+        let mut object = serde_json::Map::new();
+
+        for key in &keys {
+            let value = json_value(&value(key));
+
+            if config.build.aliases {
+                for alias in key.aliases() {
+                    object.insert(alias, value.to_owned());
+                }
             }
+
+            object.insert(key.key.to_owned(), value);
+        }
+
+        let object = serde_json::Value::Object(object);
+
+        if minify {
+            return format!("{object}\n");
         }
 
-        file.push_str(&format!("{}={}\n", key.key, key.value.to_string()));
+        return format!("{}\n", serde_json::to_string_pretty(&object).unwrap());
+    }
+
+    let mut file = String::new();
+
+    let header = files::header(commands::source_name(&config.src));
+
+    // Protected builds keep the header when minified so they can be overwritten by the next build
+    if !minify {
+        file.push_str(&format!("{header}\n\n"))
+    } else if config.build.protect {
+        file.push_str(&format!("{header}\n"))
     }
 
-    fs::write(&options.config.build.output, file).unwrap();
+    for key in &keys {
+        if !minify {
+            for constraint in &key.decorators {
+                file.push_str(&format!("# {constraint}\n"));
+            }
+        }
+
+        let value = value(key);
 
-    println!(
-        "Completed build wrote output to {}.",
-        options.config.build.output
+        file.push_str(&format!("{}={}\n", key.key, value));
+
+        if config.build.aliases {
+            for alias in key.aliases() {
+                if !minify {
+                    file.push_str(&format!("# Previous name of {}\n", key.key));
+                }
+
+                file.push_str(&format!("{alias}={value}\n"));
+            }
+        }
+    }
+
+    file
+}
+
+/// Converts the value to JSON keeping numbers and arrays typed
+fn json_value(value: &ValueType) -> serde_json::Value {
+    let number = |n: f64| {
+        serde_json::Number::from_f64(n)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null)
+    };
+
+    match value {
+        ValueType::Integer(v) => serde_json::Value::from(*v),
+        ValueType::Number(v) => number(*v),
+        ValueType::String(v) => serde_json::Value::from(v.to_owned()),
+        ValueType::StringArray(v) => serde_json::Value::from(v.to_owned()),
+        ValueType::IntegerArray(v) => serde_json::Value::from(v.to_owned()),
+        ValueType::NumberArray(v) => serde_json::Value::Array(v.iter().map(|n| number(*n)).collect()),
+    }
+}
+
+/// Reads the keys and their values from the output of a target
+fn read_output(format: TargetFormat, content: &str) -> Result<Vec<(String, String)>, String> {
+    match format {
+        // The generated output is valid source syntax so it can be parsed the same way
+        TargetFormat::Dotenv => Ok(parsing::parse(content)
+            .resolve(&Environment::All)
+            .into_iter()
+            .map(|k| (k.key, k.value.to_string()))
+            .collect()),
+        TargetFormat::Json => {
+            let object: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(content).map_err(|err| err.to_string())?;
+
+            Ok(object.into_iter().map(|(k, v)| (k, v.to_string())).collect())
+        }
+    }
+}
+
+/// Converts the value to its canonical unit if it is decorated with a unit decorator
+fn normalize(key: &Key) -> ValueType {
+    key.decorators
+        .iter()
+        .find_map(|d| Unit::from_decorator(&d.key))
+        .and_then(|unit| unit.normalize(&key.value))
+        .unwrap_or(key.value.to_owned())
+}
+
+/// Re-runs the build of the target in memory and reports any drift from the existing output
+///
+/// Returns true if the output is up to date.
+fn verify(options: &Options, target: &Target, content: &str) -> bool {
+    let output = &target.output;
+    let environment = target.environment_or(&options.environment);
+
+    let src = commands::source_name(&options.config.src);
+
+    println!("Checking '{output}' against '{src}'...");
+
+    let existing = match fs::read(output) {
+        Ok(existing) => String::from_utf8(existing).unwrap(),
+        Err(_) => {
+            println!(
+                "{} '{output}' does not exist. Run `vnv build` to create it.",
+                "ERROR:".bold().red()
+            );
+            return false;
+        }
+    };
+
+    let expected = read_output(
+        target.format,
+        &generate(&options.config, target, &environment, content),
     )
+    .unwrap_or_default();
+
+    let found = match read_output(target.format, &existing) {
+        Ok(found) => found,
+        Err(err) => {
+            println!(
+                "{} '{output}' can't be read: {err}. Run `vnv build` to regenerate it.",
+                "ERROR:".bold().red()
+            );
+            return false;
+        }
+    };
+
+    let mut added = 0;
+    let mut removed = 0;
+    let mut changed = 0;
+
+    // The decorators are only comments in the output so the aliases are read from the source
+    let source = parsing::parse(content).resolve(&environment);
+    let aliases = |key: &str| {
+        source
+            .iter()
+            .find(|k| k.key == key)
+            .map(|k| k.aliases())
+            .unwrap_or_default()
+    };
+
+    // Values are cloaked the same way as in `vnv check`
+    let hidden = |key: &str| source.iter().any(|k| k.key == key && options.config.cloak.hides(k));
+
+    // Keys that are still written under a previous name are accepted during a migration
+    let renamed = |key: &str| {
+        aliases(key)
+            .into_iter()
+            .find_map(|alias| found.iter().find(|(k, _)| *k == alias))
+    };
+
+    for (key, expected_value) in &expected {
+        let display_value = |value: &str| {
+            if hidden(key) {
+                return mask(value, options.config.cloak_reveal);
+            }
+
+            value.to_owned()
+        };
+
+        let existing = found.iter().find(|(k, _)| k == key).or_else(|| {
+            let old = renamed(key);
+
+            if let Some((old, _)) = old {
+                println!(
+                    "{} {} uses the previous name of {}. Rename it to {} before the alias is removed.",
+                    old,
+                    "!".yellow().bold(),
+                    key,
+                    key
+                );
+            }
+
+            old
+        });
+
+        match existing {
+            Some((_, found_value)) => {
+                if expected_value != found_value {
+                    changed += 1;
+                    println!(
+                        "{} {} expected {} found {}",
+                        key,
+                        "~".yellow().bold(),
+                        display_value(expected_value),
+                        display_value(found_value)
+                    );
+                }
+            }
+            None => {
+                removed += 1;
+                println!("{} {} removed from {output}", key, "-".red().bold());
+            }
+        }
+    }
+
+    for (key, _) in &found {
+        let is_alias = expected.iter().any(|(k, _)| aliases(k).contains(key));
+
+        if !expected.iter().any(|(k, _)| k == key) && !is_alias {
+            added += 1;
+            println!("{} {} added to {output}", key, "+".green().bold());
+        }
+    }
+
+    if added + removed + changed > 0 {
+        println!(
+            "{} '{output}' has drifted from '{src}': {added} added, {removed} removed, {changed} changed. Run `vnv build` to regenerate it.",
+            "ERROR:".bold().red()
+        );
+        return false;
+    }
+
+    println!("'{output}' is up to date.");
+
+    true
 }
@@ -1,40 +1,95 @@
 use colored::Colorize;
 use core::panic;
-use std::{fs, time::Instant};
+use std::time::Instant;
 use vnv::{
-    parsing::{self, config, Environment, ValueType},
-    util,
+    diagnostics::{self, Diagnostic, Format, Renderer},
+    parsing::{self, config, naming, Environment},
 };
 
+use crate::commands;
+
 #[derive(Debug)]
 pub struct Options {
     pub config: config::Options,
     pub environment: Environment,
+    pub format: Format,
+    /// Source that was already read, it is read from `config.src` if not provided
+    pub content: Option<String>,
+    /// Writes the report to stderr so stdout only has the output of the command that ran the check
+    pub stderr: bool,
 }
 
 // src file does not match template file. If this is intended you can run `vnv template` to re-create the template file based on the src file.
 
 pub fn default(options: Options) {
-    let cloak = options.config.cloak;
-
     let now = Instant::now();
 
-    println!("Checking '{}'...", options.config.src);
+    let content = match options.content {
+        Some(content) => content,
+        None => commands::read_source(&options.config.src),
+    };
+
+    let print = |line: String| {
+        if options.stderr {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    };
+
+    let mut result = parsing::parse(&content);
 
-    let src_contents = fs::read(&options.config.src).expect("Error reading file");
+    naming::apply(&mut result, &content, &options.config.naming);
 
-    let content = String::from_utf8(src_contents).unwrap();
+    // Keys with @sensitive are hidden even when cloak is disabled
+    let renderer = Renderer::new(&content, commands::source_name(&options.config.src)).cloak(
+        &result.keys,
+        options.config.cloak,
+        options.config.cloak_reveal,
+    );
 
-    let result = parsing::parse(&content);
+    let all: Vec<Diagnostic> = diagnostics::collect(&result, &options.environment);
 
-    let mut valid = true; 
+    if options.format != Format::Terminal {
+        print(renderer.render(options.format, &all));
 
-    for key in result.keys {
-        let environment_does_not_match = key.environment != options.environment;
-        let key_environment_not_all = key.environment != Environment::All;
+        if diagnostics::has_errors(&all) {
+            panic!("Check failed!");
+        }
+
+        return;
+    }
 
-        if environment_does_not_match && key_environment_not_all {
-            println!("{} ⏭️", key.key.truecolor(125, 125, 125));
+    print(format!("Checking '{}'...", commands::source_name(&options.config.src)));
+
+    let mut valid = true;
+
+    for diagnostic in result.errors.iter().chain(result.warnings.iter()) {
+        if diagnostic.is_error() {
+            valid = false;
+        }
+
+        print(renderer.terminal(diagnostic));
+    }
+
+    let active = result.active(&options.environment);
+
+    for (key, active) in result.keys.into_iter().zip(active) {
+        if !active {
+            // Keys in the environment are skipped because of their @when conditions
+            let reason = if key.included_in(&options.environment) {
+                let conditions: Vec<String> = key
+                    .conditions()
+                    .iter()
+                    .map(|(name, value)| format!("{name} is {}", value.raw()))
+                    .collect();
+
+                format!(" (when {})", conditions.join(" and "))
+            } else {
+                String::new()
+            };
+
+            print(format!("{} ⏭️{}", key.key.truecolor(125, 125, 125), reason.truecolor(125, 125, 125)));
             continue; // skip this key
         }
 
@@ -43,135 +98,21 @@ pub fn default(options: Options) {
             status.push_str("✔️");
         } else {
             valid = false;
-            status.push_str("❌");
+            status.push('❌');
         }
-        println!("{} {status}", key.key);
-        for err in key.errors.clone() {
-            let lines: Vec<&str> = content.split('\n').collect();
-            let index = key.position.line - 1;
-            let mut line = String::new();
-            let mut error_squiggles = String::new();
-
-            let string_val = match &key.value {
-                ValueType::Number(v) => v.to_string(),
-                ValueType::String(v) => format!("\"{v}\""),
-                ValueType::StringArray(v) => format!("{:?}", v),
-                ValueType::NumberArray(v) => format!("{:?}", v),
-            };
+        print(format!("{} {status}", key.key));
 
-            for (i, l) in lines.into_iter().enumerate() {
-                if i == index as usize {
-                    if let Some(val) = err.value {
-                        let string_problem_val = match val {
-                            ValueType::Number(v) => v.to_string(),
-                            ValueType::String(v) => format!("\"{v}\""),
-                            // These wont ever be hit but need to be implemented anyways
-                            ValueType::StringArray(v) => format!("{:?}", v),
-                            ValueType::NumberArray(v) => format!("{:?}", v),
-                        };
-
-                        let start_index = string_val.find(&string_problem_val);
-
-                        if let Some(index) = start_index {
-                            // Calculates the start from the '=' and then adds the index
-                            let offset: u32 = key.key.len() as u32 + 1 + index as u32;
-
-                            // Left pad
-                            for _ in 0..offset {
-                                error_squiggles.push_str(" ");
-                            }
-
-                            for _ in 0..string_problem_val.len() {
-                                error_squiggles.push_str("^");
-                            }
-                        }
-
-                        if cloak {
-                            let mut cloaked = String::new();
-
-                            for _ in 0..string_val.len() {
-                                cloaked.push_str("*");
-                            }
-
-                            line = format!("{}={}", key.key, cloaked);
-                        } else {
-                            line = format!("{}={string_val}", key.key);
-                        }
-                    } else {
-                        if cloak {
-                            let found_index = l.find("=");
-
-                            if let Some(index) = found_index {
-                                let start = &l[..index + 1];
-
-                                let mut cloaked = String::new();
-
-                                for _ in 0..string_val.len() {
-                                    cloaked.push_str("*");
-                                }
-
-                                line = format!("{start}{cloaked}");
-                            }
-                        } else {
-                            line = l.to_string();
-                        }
-                    }
-                    break;
-                }
-            }
-
-            let error_str: String;
-
-            if cloak {
-                let trimmed_val = util::trim_quotes(&string_val);
-                let found_index = err.message.find(&trimmed_val);
-
-                if let Some(index) = found_index {
-                    println!("{} found at index {}", err.message, index);
-
-                    let start = &err.message[..index];
-                    let mut cloaked = String::new();
-
-                    for _ in 0..string_val.len() {
-                        cloaked.push_str("*");
-                    }
-                    let end = &err.message[index + trimmed_val.len()..];
-
-                    error_str = format!("{start}{cloaked}{end}");
-                } else {
-                    error_str = err.message;
-                }
-            } else {
-                error_str = format!("{}", err.message);
-            }
-
-            let ascii_error = "ERROR".red().bold();
-            let ascii_line = "|".blue();
-            let ascii_arrow = "-->".blue();
-            let error_message = format!(
-                r#"{ascii_error}: {}
-{ascii_arrow} {}:{}:{}
-     {ascii_line}
-{}   {ascii_line}  {line}
-     {ascii_line}  {}         
-"#,
-                error_str.bold(),
-                options.config.src,
-                key.position.line,
-                key.position.column,
-                util::number_pad(key.position.line, 2).to_string().blue(),
-                error_squiggles.red()
-            );
-            println!("{error_message}");
+        for err in &key.errors {
+            print(renderer.terminal(err));
         }
     }
 
     let elapsed = now.elapsed();
 
     if !valid {
-        println!("Check completed in {:.2?}", elapsed);
+        print(format!("Check completed in {:.2?}", elapsed));
         panic!("Check failed!");
     } else {
-        println!("Completed in {:.2?}", elapsed);
+        print(format!("Completed in {:.2?}", elapsed));
     }
 }
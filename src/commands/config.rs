@@ -0,0 +1,153 @@
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use vnv::parsing::config::{self, Origin, Resolved};
+
+/// Flags that override the config, each matches a `VNV_*` environment variable
+#[derive(Args, Debug)]
+pub struct Overrides {
+    /// Path of the source file [env: VNV_SRC]
+    #[clap(long, value_parser, global = true, help_heading = "Config overrides")]
+    src: Option<String>,
+
+    /// Hides the values of keys in the output, "private" only hides private keys [env: VNV_CLOAK]
+    #[clap(short, long, value_parser, global = true, num_args = 0..=1, require_equals = true, default_missing_value = "true", help_heading = "Config overrides")]
+    cloak: Option<String>,
+
+    /// Shows the first and last N characters of hidden values [env: VNV_CLOAK_REVEAL]
+    #[clap(long, value_parser, global = true, help_heading = "Config overrides")]
+    cloak_reveal: Option<String>,
+
+    /// Path of the built .env file [env: VNV_BUILD_OUTPUT]
+    #[clap(long, value_parser, global = true, help_heading = "Config overrides")]
+    build_output: Option<String>,
+
+    /// Leaves out comments and blank lines from the build [env: VNV_BUILD_MINIFY]
+    #[clap(long, value_parser, global = true, num_args = 0..=1, require_equals = true, default_missing_value = "true", help_heading = "Config overrides")]
+    build_minify: Option<String>,
+
+    /// Converts values with units to their canonical unit in the build [env: VNV_BUILD_NORMALIZE]
+    #[clap(long, value_parser, global = true, num_args = 0..=1, require_equals = true, default_missing_value = "true", help_heading = "Config overrides")]
+    build_normalize: Option<String>,
+
+    /// Also writes keys under their previous names in the build [env: VNV_BUILD_ALIASES]
+    #[clap(long, value_parser, global = true, num_args = 0..=1, require_equals = true, default_missing_value = "true", help_heading = "Config overrides")]
+    build_aliases: Option<String>,
+
+    /// Refuses to overwrite output files that weren't generated by vnv [env: VNV_BUILD_PROTECT]
+    #[clap(long, value_parser, global = true, num_args = 0..=1, require_equals = true, default_missing_value = "true", help_heading = "Config overrides")]
+    build_protect: Option<String>,
+
+    /// Requires keys to be in SCREAMING_SNAKE_CASE [env: VNV_NAMING_SCREAMING_SNAKE_CASE]
+    #[clap(long, value_parser, global = true, num_args = 0..=1, require_equals = true, default_missing_value = "true", help_heading = "Config overrides")]
+    naming_screaming_snake_case: Option<String>,
+
+    /// Prefix required for public keys, empty for none [env: VNV_NAMING_PREFIXES_PUBLIC]
+    #[clap(long, value_parser, global = true, help_heading = "Config overrides")]
+    naming_prefixes_public: Option<String>,
+
+    /// Prefix required for private keys, empty for none [env: VNV_NAMING_PREFIXES_PRIVATE]
+    #[clap(long, value_parser, global = true, help_heading = "Config overrides")]
+    naming_prefixes_private: Option<String>,
+
+    /// Comma separated names that can't be used as keys [env: VNV_NAMING_FORBIDDEN]
+    #[clap(long, value_parser, global = true, help_heading = "Config overrides")]
+    naming_forbidden: Option<String>,
+}
+
+impl Overrides {
+    /// Returns the fields set by the flags along with their values
+    pub fn flags(&self) -> Vec<(&'static str, String)> {
+        let values = [
+            &self.src,
+            &self.cloak,
+            &self.cloak_reveal,
+            &self.build_output,
+            &self.build_minify,
+            &self.build_normalize,
+            &self.build_aliases,
+            &self.build_protect,
+            &self.naming_screaming_snake_case,
+            &self.naming_prefixes_public,
+            &self.naming_prefixes_private,
+            &self.naming_forbidden,
+        ];
+
+        config::FIELDS
+            .into_iter()
+            .zip(values)
+            .filter_map(|(field, value)| value.as_ref().map(|v| (field, v.to_owned())))
+            .collect()
+    }
+}
+
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum Action {
+    /// Print the effective config and where each value came from
+    Show,
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub resolved: Resolved,
+    pub action: Action,
+}
+
+/// Prints the merged config with the origin of each field
+fn show(resolved: &Resolved) {
+    match &resolved.path {
+        Some(path) => println!("Config file: {}\n", path.display()),
+        None => println!("Config file: none found, using the defaults\n"),
+    }
+
+    let values: Vec<String> = resolved
+        .origins
+        .iter()
+        .map(|(field, _)| resolved.options.get(field).unwrap_or_default())
+        .collect();
+
+    let width = config::FIELDS.iter().map(|f| f.len()).max().unwrap_or_default();
+    let value_width = values.iter().map(|v| v.len().max("(none)".len())).max().unwrap_or_default();
+
+    for ((field, origin), value) in resolved.origins.iter().zip(values) {
+        let value = if value.is_empty() {
+            format!("{:value_width$}", "(none)").dimmed()
+        } else {
+            format!("{value:value_width$}").normal()
+        };
+
+        let origin = match origin {
+            Origin::Default => origin.to_string().dimmed(),
+            _ => origin.to_string().cyan(),
+        };
+
+        println!("{field:width$}  {value}  {origin}");
+    }
+
+    if !resolved.options.build.targets.is_empty() {
+        println!("\nBuild targets:");
+
+        for target in resolved.options.build.targets() {
+            let environment = match &target.environment {
+                Some(environment) => format!("{environment} environment"),
+                None => String::from("environment from --dev/--prod"),
+            };
+
+            println!(
+                "  {}  {} ({}, {environment})",
+                target.name, target.output, target.format
+            );
+        }
+    }
+
+    println!(
+        "\n{}",
+        "Precedence: flags > VNV_* environment variables > config file > defaults".dimmed()
+    );
+}
+
+/// Runs the config subcommand
+pub fn default(options: Options) {
+    match options.action {
+        Action::Show => show(&options.resolved),
+    }
+}
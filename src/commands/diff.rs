@@ -0,0 +1,150 @@
+use colored::Colorize;
+use std::process;
+use vnv::decorators;
+use vnv::diagnostics::Renderer;
+use vnv::parsing::{self, config, Environment, Key};
+
+use crate::commands;
+
+#[derive(Debug)]
+pub struct Options {
+    pub config: config::Options,
+    pub left: Environment,
+    pub right: Environment,
+    /// Fails when the right environment is missing keys that are defined for the left environment
+    pub fail_on_missing: bool,
+}
+
+/// Returns the decorators of a key that are relevant for comparing environments
+///
+/// Environment decorators are left out since they are expected to differ and documentation decorators since they don't validate.
+fn constraints(key: &Key) -> Vec<String> {
+    let mut constraints: Vec<String> = key
+        .decorators
+        .iter()
+        .filter(|d| d.key != "dev" && d.key != "prod" && !decorators::DOCUMENTATION.contains(&d.key.as_str()))
+        .map(|d| d.to_string())
+        .collect();
+
+    constraints.sort();
+
+    constraints
+}
+
+pub fn default(options: Options) {
+    println!(
+        "Comparing '{}' and '{}' in '{}'...",
+        options.left, options.right, commands::source_name(&options.config.src)
+    );
+
+    let content = commands::read_source(&options.config.src);
+
+    let result = parsing::parse(&content);
+
+    // Diffing a source with syntax errors would silently compare partial keys
+    if !result.errors.is_empty() {
+        let renderer = Renderer::new(&content, commands::source_name(&options.config.src)).cloak(
+            &result.keys,
+            options.config.cloak,
+            options.config.cloak_reveal,
+        );
+
+        for diagnostic in &result.errors {
+            println!("{}", renderer.terminal(diagnostic));
+        }
+
+        println!(
+            "{} '{}' has errors and can't be compared.",
+            "ERROR:".bold().red(),
+            commands::source_name(&options.config.src)
+        );
+        process::exit(1);
+    }
+
+    let left = result.resolve(&options.left);
+    let right = result.resolve(&options.right);
+
+    let mut missing_right = 0;
+    let mut differences = 0;
+
+    for key in &left {
+        let other = right.iter().find(|k| k.key == key.key);
+
+        match other {
+            Some(other) => {
+                let mut messages: Vec<String> = Vec::new();
+
+                if key.value.type_name() != other.value.type_name() {
+                    messages.push(format!(
+                        "type differs: {} ({}) vs {} ({})",
+                        key.value.type_name(),
+                        options.left,
+                        other.value.type_name(),
+                        options.right
+                    ));
+                }
+
+                let left_constraints = constraints(key);
+                let right_constraints = constraints(other);
+
+                if left_constraints != right_constraints {
+                    messages.push(format!(
+                        "decorators differ: [{}] ({}) vs [{}] ({})",
+                        left_constraints.join(" "),
+                        options.left,
+                        right_constraints.join(" "),
+                        options.right
+                    ));
+                }
+
+                if messages.is_empty() {
+                    println!("{} ✔️", key.key);
+                } else {
+                    differences += 1;
+                    println!("{} {}", key.key, "~".yellow().bold());
+                    for message in messages {
+                        println!("    {message}");
+                    }
+                }
+            }
+            None => {
+                missing_right += 1;
+                println!(
+                    "{} {} missing from {}",
+                    key.key,
+                    "-".red().bold(),
+                    options.right
+                );
+            }
+        }
+    }
+
+    let mut missing_left = 0;
+
+    for key in &right {
+        if !left.iter().any(|k| k.key == key.key) {
+            missing_left += 1;
+            println!(
+                "{} {} missing from {}",
+                key.key,
+                "+".green().bold(),
+                options.left
+            );
+        }
+    }
+
+    println!(
+        "{missing_right} missing from {}, {missing_left} missing from {}, {differences} changed.",
+        options.right, options.left
+    );
+
+    if options.fail_on_missing && missing_right > 0 {
+        println!(
+            "{} '{}' is missing keys that are defined for '{}'.",
+            "ERROR:".bold().red(),
+            options.right,
+            options.left
+        );
+        process::exit(1);
+    }
+}
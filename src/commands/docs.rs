@@ -0,0 +1,269 @@
+use colored::Colorize;
+use std::{fs, process, str::FromStr};
+use vnv::decorators;
+use vnv::parsing::{self, config, Environment, Key, Scope};
+
+use crate::commands;
+
+/// The formats the documentation can be generated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsFormat {
+    Markdown,
+    Html,
+}
+
+impl FromStr for DocsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Ok(DocsFormat::Markdown),
+            "html" => Ok(DocsFormat::Html),
+            _ => Err(format!(
+                "'{s}' is not a valid format. Expected one of 'markdown' or 'html'."
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub config: config::Options,
+    pub format: DocsFormat,
+    /// Path to write the documentation to, it is printed if not provided
+    pub output: Option<String>,
+}
+
+/// A key with every definition of it across environments
+struct Row {
+    key: String,
+    scope: String,
+    environments: String,
+    value_type: String,
+    constraints: Vec<String>,
+    description: Option<String>,
+    example: Option<String>,
+    owner: Option<String>,
+    deprecated: Option<String>,
+    aliases: Vec<String>,
+}
+
+/// Returns the unique values in the order they were found
+fn unique(values: impl Iterator<Item = String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+
+    for value in values {
+        if !result.contains(&value) {
+            result.push(value);
+        }
+    }
+
+    result
+}
+
+/// Groups the definitions of each key into a row in the order they are first defined
+fn rows(keys: &[Key]) -> Vec<Row> {
+    let names = unique(keys.iter().map(|k| k.key.to_owned()));
+
+    names
+        .into_iter()
+        .map(|name| {
+            let definitions: Vec<&Key> = keys.iter().filter(|k| k.key == name).collect();
+
+            let environments = if definitions.iter().any(|k| k.environment == Environment::All) {
+                String::from("all")
+            } else {
+                unique(definitions.iter().map(|k| k.environment.to_string())).join(", ")
+            };
+
+            let scope = unique(definitions.iter().map(|k| match k.scope {
+                Scope::Public => String::from("public"),
+                Scope::Private => String::from("private"),
+            }))
+            .join(", ");
+
+            let constraints = unique(definitions.iter().flat_map(|k| {
+                k.decorators
+                    .iter()
+                    .filter(|d| {
+                        !matches!(d.key.as_str(), "public" | "private" | "dev" | "prod" | "alias")
+                            && !decorators::DOCUMENTATION.contains(&d.key.as_str())
+                    })
+                    .map(|d| d.to_string())
+            }));
+
+            let annotation = |name: &str| definitions.iter().find_map(|k| k.annotation(name));
+
+            Row {
+                key: name.to_owned(),
+                scope,
+                environments,
+                value_type: unique(definitions.iter().map(|k| k.value.type_name().to_owned()))
+                    .join(" | "),
+                constraints,
+                description: annotation("description"),
+                example: annotation("example"),
+                owner: annotation("owner"),
+                deprecated: annotation("deprecated"),
+                aliases: unique(definitions.iter().flat_map(|k| k.aliases())),
+            }
+        })
+        .collect()
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Wraps the text in a Markdown code span
+///
+/// Backticks can't be escaped inside a code span so the span is fenced with more backticks than the longest
+/// run in the text.
+fn code(text: &str) -> String {
+    let text = escape_markdown(text);
+
+    if !text.contains('`') {
+        return format!("`{text}`");
+    }
+
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or_default();
+    let fence = "`".repeat(longest + 1);
+
+    format!("{fence} {text} {fence}")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Renders the rows as a Markdown table
+fn markdown(src: &str, rows: &[Row]) -> String {
+    let mut output = format!(
+        "# Environment Variables\n\nGenerated from `{src}` by vnv.\n\n| Key | Scope | Environments | Type | Constraints | Description |\n| --- | --- | --- | --- | --- | --- |\n"
+    );
+
+    for row in rows {
+        let constraints: Vec<String> = row
+            .constraints
+            .iter()
+            .map(|c| code(c))
+            .collect();
+
+        let mut description: Vec<String> = Vec::new();
+
+        if let Some(deprecated) = &row.deprecated {
+            description.push(format!("**Deprecated:** {}", escape_markdown(deprecated)));
+        }
+        if let Some(text) = &row.description {
+            description.push(escape_markdown(text));
+        }
+        if let Some(example) = &row.example {
+            description.push(format!("Example: {}", code(example)));
+        }
+        if let Some(owner) = &row.owner {
+            description.push(format!("Owner: {}", escape_markdown(owner)));
+        }
+        if !row.aliases.is_empty() {
+            let aliases: Vec<String> = row.aliases.iter().map(|a| code(a)).collect();
+
+            description.push(format!("Previously: {}", aliases.join(", ")));
+        }
+
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            code(&row.key),
+            row.scope,
+            row.environments,
+            escape_markdown(&row.value_type),
+            constraints.join(" "),
+            description.join("<br>")
+        ));
+    }
+
+    output
+}
+
+/// Renders the rows as an HTML table
+fn html(src: &str, rows: &[Row]) -> String {
+    let mut output = format!(
+        "<!-- Generated from '{}' by vnv. -->\n<table>\n  <thead>\n    <tr><th>Key</th><th>Scope</th><th>Environments</th><th>Type</th><th>Constraints</th><th>Description</th></tr>\n  </thead>\n  <tbody>\n",
+        escape_html(src)
+    );
+
+    for row in rows {
+        let constraints: Vec<String> = row
+            .constraints
+            .iter()
+            .map(|c| format!("<code>{}</code>", escape_html(c)))
+            .collect();
+
+        let mut description: Vec<String> = Vec::new();
+
+        if let Some(deprecated) = &row.deprecated {
+            description.push(format!("<strong>Deprecated:</strong> {}", escape_html(deprecated)));
+        }
+        if let Some(text) = &row.description {
+            description.push(escape_html(text));
+        }
+        if let Some(example) = &row.example {
+            description.push(format!("Example: <code>{}</code>", escape_html(example)));
+        }
+        if let Some(owner) = &row.owner {
+            description.push(format!("Owner: {}", escape_html(owner)));
+        }
+        if !row.aliases.is_empty() {
+            let aliases: Vec<String> = row.aliases.iter().map(|a| format!("<code>{a}</code>")).collect();
+
+            description.push(format!("Previously: {}", aliases.join(", ")));
+        }
+
+        output.push_str(&format!(
+            "    <tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&row.key),
+            row.scope,
+            row.environments,
+            escape_html(&row.value_type),
+            constraints.join(" "),
+            description.join("<br>")
+        ));
+    }
+
+    output.push_str("  </tbody>\n</table>\n");
+
+    output
+}
+
+/// Generates documentation of the keys from the source file
+pub fn default(options: Options) {
+    let content = commands::read_source(&options.config.src);
+
+    let result = parsing::parse(&content);
+
+    if !result.errors.is_empty() {
+        println!(
+            "{} '{}' has errors. Run `vnv check` to see them.",
+            "ERROR:".bold().red(),
+            commands::source_name(&options.config.src)
+        );
+        process::exit(1);
+    }
+
+    let rows = rows(&result.keys);
+
+    let docs = match options.format {
+        DocsFormat::Markdown => markdown(commands::source_name(&options.config.src), &rows),
+        DocsFormat::Html => html(commands::source_name(&options.config.src), &rows),
+    };
+
+    match options.output {
+        Some(output) => {
+            fs::write(&output, docs).unwrap();
+
+            println!("Wrote documentation for {} key(s) to {output}.", rows.len());
+        }
+        None => print!("{docs}"),
+    }
+}
@@ -24,12 +24,9 @@ const DEFAULT_GIT_IGNORE: &str = r#".vnv
 
 /// Initializes the config file and optionally a template file
 pub fn default() {
-    match fs::read(CONFIG_PATH) {
-        Ok(_) => {
-            println!("{} vnv already initialized.", "Error:".bold().red());
-            return;
-        }
-        _ => {}
+    if fs::read(CONFIG_PATH).is_ok() {
+        println!("{} vnv already initialized.", "Error:".bold().red());
+        return;
     };
 
     println!("{INIT_MESSAGE}");
@@ -58,8 +55,8 @@ pub fn default() {
     request_value(&mut config.build.output, "Where to write the build output?");
 
     match ask_yes_no("Hide environment variables in std out", Answer::No) {
-        Answer::Yes => config.cloak = true,
-        Answer::No => config.cloak = false,
+        Answer::Yes => config.cloak = config::Cloak::All,
+        Answer::No => config.cloak = config::Cloak::Off,
     }
 
     match ask_yes_no("Keep comments and decorator comments in .env", Answer::Yes) {
@@ -107,5 +104,5 @@ pub fn default() {
 
     println!("Writing preferences to {CONFIG_PATH}.");
 
-    fs::write(&CONFIG_PATH, config_content).unwrap();
+    fs::write(CONFIG_PATH, config_content).unwrap();
 }
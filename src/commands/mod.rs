@@ -1,4 +1,13 @@
 use clap::Subcommand;
+use colored::Colorize;
+use std::{
+    fs,
+    io::{self, Read},
+    process,
+};
+use vnv::{diagnostics::Format, parsing::Environment};
+
+use docs::DocsFormat;
 
 pub mod check;
 
@@ -12,22 +21,73 @@ pub mod init;
 
 pub use init::default as init;
 
+pub mod diff;
+
+pub use diff::default as diff;
+
+pub mod audit;
+
+pub use audit::default as audit;
+
+pub mod docs;
+
+pub use docs::default as docs;
+
+pub mod config;
+
+pub use config::default as config;
+
+/// The source path that reads the source from stdin
+pub const STDIN: &str = "-";
+
+/// Returns the name of the source to show in messages
+pub fn source_name(src: &str) -> &str {
+    if src == STDIN {
+        return "<stdin>";
+    }
+
+    src
+}
+
+/// Reads the source file, or stdin when the path is `-`
+pub fn read_source(src: &str) -> String {
+    let result = if src == STDIN {
+        let mut content = String::new();
+
+        io::stdin().read_to_string(&mut content).map(|_| content)
+    } else {
+        fs::read_to_string(src)
+    };
+
+    match result {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!(
+                "{} Couldn't read '{}': {err}",
+                "ERROR:".bold().red(),
+                source_name(src)
+            );
+            process::exit(1);
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Validate the .vnv file
     Check {
-        /// Path of file to validate, defaults to ".vnv" if not specified.
+        /// Path of file to validate, defaults to ".vnv" if not specified. Use "-" to read from stdin.
         #[clap(short, long, value_parser)]
         file: Option<String>,
 
-        /// Will hide the values in the ".vnv" in the std out
-        #[clap(short, long, action = clap::ArgAction::SetTrue)]
-        cloak: bool,
-
         #[clap(short, long, action = clap::ArgAction::SetTrue)]
         dev: bool,
         #[clap(short, long, action = clap::ArgAction::SetTrue)]
         prod: bool,
+
+        /// Output format of the diagnostics: "terminal", "json" or "lsp". Defaults to "terminal".
+        #[clap(long, value_parser)]
+        format: Option<Format>,
     },
     /// Convert the .vnv file to a valid .env file
     Build {
@@ -35,7 +95,66 @@ pub enum Commands {
         dev: bool,
         #[clap(short, long, action = clap::ArgAction::SetTrue)]
         prod: bool,
+
+        /// Compares the existing output to the build and fails if they differ instead of writing it
+        #[clap(long, action = clap::ArgAction::SetTrue)]
+        check: bool,
+
+        /// Converts durations, byte sizes and percentages to milliseconds, bytes and ratios. Shorthand for `--build-normalize`
+        #[clap(long, action = clap::ArgAction::SetTrue)]
+        normalize: bool,
+
+        /// Also writes keys under their previous names from `@alias` during a migration. Shorthand for `--build-aliases`
+        #[clap(long, action = clap::ArgAction::SetTrue)]
+        aliases: bool,
+
+        /// Name of the build target to build, builds every target if not specified.
+        #[clap(short, long, value_parser)]
+        target: Option<String>,
+
+        /// Writes the build to stdout instead of the output file, everything else is written to stderr
+        #[clap(long, action = clap::ArgAction::SetTrue, conflicts_with = "check")]
+        stdout: bool,
     },
     /// Initializes .vnv by creating the source file and settings file as well as configuring your .gitignore
     Init {},
+    /// Compare the keys of two environments
+    Diff {
+        /// Environments to compare, defaults to "--env dev --env prod" if not specified.
+        #[clap(short, long = "env", value_parser, num_args = 1)]
+        env: Vec<Environment>,
+
+        /// Path of file to compare, defaults to ".vnv" if not specified.
+        #[clap(short, long, value_parser)]
+        file: Option<String>,
+
+        /// Exits with an error when the second environment is missing keys defined for the first
+        #[clap(long, action = clap::ArgAction::SetTrue)]
+        fail_on_missing: bool,
+    },
+    /// Find public keys whose values look like secrets
+    Audit {
+        /// Path of file to audit, defaults to ".vnv" if not specified.
+        #[clap(short, long, value_parser)]
+        file: Option<String>,
+    },
+    /// Generate documentation of the keys from the .vnv file
+    Docs {
+        /// Path of file to document, defaults to ".vnv" if not specified.
+        #[clap(short, long, value_parser)]
+        file: Option<String>,
+
+        /// Format of the documentation: "markdown" or "html". Defaults to "markdown".
+        #[clap(long, value_parser)]
+        format: Option<DocsFormat>,
+
+        /// Path to write the documentation to, it is printed if not specified.
+        #[clap(short, long, value_parser)]
+        output: Option<String>,
+    },
+    /// Inspect the config
+    Config {
+        #[clap(subcommand)]
+        action: config::Action,
+    },
 }
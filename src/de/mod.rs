@@ -0,0 +1,315 @@
+use crate::diagnostics::{self, Diagnostic};
+use crate::parsing::{self, Environment, FilePosition, Key, ValueType};
+use serde::de::{
+    self, value::SeqDeserializer, DeserializeOwned, IntoDeserializer, MapAccess, Visitor,
+};
+use std::fmt;
+
+/// An error that occurred while deserializing the keys of a .vnv file
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// The file failed validation
+    Invalid(Vec<Diagnostic>),
+    /// A key couldn't be deserialized into the requested type
+    Custom {
+        message: String,
+        /// The key that couldn't be deserialized
+        key: Option<String>,
+        position: Option<FilePosition>,
+    },
+}
+
+impl Error {
+    /// Attaches the key to the error if it isn't already attached to one
+    fn at(self, key: &Key) -> Self {
+        match self {
+            Error::Custom {
+                message,
+                key: None,
+                position: None,
+            } => Error::Custom {
+                message,
+                key: Some(key.key.to_owned()),
+                position: Some(key.position),
+            },
+            _ => self,
+        }
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Custom {
+            message: msg.to_string(),
+            key: None,
+            position: None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Invalid(diagnostics) => {
+                let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+
+                write!(f, "{}", messages.join("\n"))
+            }
+            Error::Custom {
+                message,
+                key: Some(key),
+                position: Some(position),
+            } => write!(f, "{}:{}: {key}: {message}", position.line, position.column),
+            Error::Custom { message, .. } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Deserializes the keys resolved for an environment as a map or struct
+///
+/// Struct fields match keys ignoring case so `port` is read from `PORT`.
+pub struct Deserializer {
+    keys: Vec<Key>,
+}
+
+impl Deserializer {
+    pub fn new(keys: Vec<Key>) -> Self {
+        Deserializer { keys }
+    }
+}
+
+struct KeyMap<'a> {
+    entries: Vec<(String, &'a Key)>,
+    index: usize,
+}
+
+impl<'de> MapAccess<'de> for KeyMap<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.entries.get(self.index) {
+            Some((name, _)) => seed.deserialize(name.to_owned().into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let (_, key) = self.entries[self.index];
+        self.index += 1;
+
+        seed.deserialize(ValueDeserializer { value: key.value.to_owned() })
+            .map_err(|err| err.at(key))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(KeyMap {
+            entries: self.keys.iter().map(|k| (k.key.to_owned(), k)).collect(),
+            index: 0,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let entries = self
+            .keys
+            .iter()
+            .map(|k| {
+                let name = fields
+                    .iter()
+                    .find(|f| f.eq_ignore_ascii_case(&k.key))
+                    .map(|f| f.to_string())
+                    .unwrap_or(k.key.to_owned());
+
+                (name, k)
+            })
+            .collect();
+
+        visitor.visit_map(KeyMap { entries, index: 0 })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// Deserializes a single value
+///
+/// Strings are parsed when a number or boolean is requested since that is how they are read from the environment.
+/// The other way around numbers and arrays can be read as strings.
+struct ValueDeserializer {
+    value: ValueType,
+}
+
+impl<'de> IntoDeserializer<'de, Error> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl ValueDeserializer {
+    fn invalid_type(&self, expected: &str) -> Error {
+        de::Error::custom(format!(
+            "Expected {expected} but found {} ({}).",
+            self.value.type_name(),
+            self.value
+        ))
+    }
+
+    fn integer<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match &self.value {
+            ValueType::Integer(v) => visitor.visit_i64(*v),
+            ValueType::Number(v) if v.fract() == 0.0 => visitor.visit_i64(*v as i64),
+            ValueType::String(v) => match v.trim().parse::<i64>() {
+                Ok(v) => visitor.visit_i64(v),
+                Err(_) => Err(self.invalid_type("an integer")),
+            },
+            _ => Err(self.invalid_type("an integer")),
+        }
+    }
+
+    fn float<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match &self.value {
+            ValueType::Integer(v) => visitor.visit_f64(*v as f64),
+            ValueType::Number(v) => visitor.visit_f64(*v),
+            ValueType::String(v) => match v.trim().parse::<f64>() {
+                Ok(v) => visitor.visit_f64(v),
+                Err(_) => Err(self.invalid_type("a number")),
+            },
+            _ => Err(self.invalid_type("a number")),
+        }
+    }
+}
+
+macro_rules! deserialize_with {
+    ($method:ident => $($name:ident)*) => {
+        $(
+            fn $name<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            ValueType::Integer(v) => visitor.visit_i64(v),
+            ValueType::Number(v) => visitor.visit_f64(v),
+            ValueType::String(v) => visitor.visit_string(v),
+            ValueType::StringArray(v) => visitor.visit_seq(SeqDeserializer::new(
+                v.into_iter().map(|s| ValueDeserializer { value: ValueType::String(s) }),
+            )),
+            ValueType::IntegerArray(v) => visitor.visit_seq(SeqDeserializer::new(
+                v.into_iter().map(|n| ValueDeserializer { value: ValueType::Integer(n) }),
+            )),
+            ValueType::NumberArray(v) => visitor.visit_seq(SeqDeserializer::new(
+                v.into_iter().map(|n| ValueDeserializer { value: ValueType::Number(n) }),
+            )),
+        }
+    }
+
+    deserialize_with!(integer => deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64);
+    deserialize_with!(float => deserialize_f32 deserialize_f64);
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match &self.value {
+            ValueType::String(v) => match v.trim().to_lowercase().as_str() {
+                "true" => visitor.visit_bool(true),
+                "false" => visitor.visit_bool(false),
+                _ => Err(self.invalid_type("a boolean")),
+            },
+            _ => Err(self.invalid_type("a boolean")),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    /// Any value can be read as a string the same way it is read from the environment
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.value.env_value())
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            ValueType::String(v) => visitor.visit_enum(v.into_deserializer()),
+            _ => Err(self.invalid_type("a string")),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 char bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Parses, validates and deserializes the keys resolved for the environment
+///
+/// Validation errors are returned as [`Error::Invalid`] with the position of each error.
+///
+/// # Examples
+/// ```
+/// use serde::Deserialize;
+/// use vnv::Env;
+///
+/// #[derive(Deserialize)]
+/// struct AppConfig {
+///     port: u16,
+///     hosts: Vec<String>,
+/// }
+///
+/// let src = "@min(1024)\nPORT=3000\n@prod\nHOSTS=[\"a.com\", \"b.com\"]";
+///
+/// let cfg: AppConfig = vnv::from_str(src, Env::Prod).unwrap();
+///
+/// assert_eq!(cfg.port, 3000);
+/// assert_eq!(cfg.hosts, vec!["a.com", "b.com"]);
+/// ```
+pub fn from_str<T: DeserializeOwned>(src: &str, environment: Environment) -> Result<T, Error> {
+    let result = parsing::parse(src);
+
+    let diagnostics = diagnostics::collect(&result, &environment);
+
+    if diagnostics::has_errors(&diagnostics) {
+        return Err(Error::Invalid(diagnostics));
+    }
+
+    T::deserialize(Deserializer::new(result.resolve(&environment)))
+}
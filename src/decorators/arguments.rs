@@ -0,0 +1,348 @@
+use crate::decorators::DecoratorValue;
+use crate::diagnostics::Span;
+use std::fmt;
+
+/// A single argument passed to a decorator
+///
+/// Arguments can either be positional `@min(5)` or named `@length(min=3)`.
+#[derive(Debug, Clone)]
+pub struct DecoratorArgument {
+    pub name: Option<String>,
+    pub value: DecoratorValue,
+    /// Offset of the argument in characters from the start of the decorator (after the `@`)
+    pub offset: usize,
+    /// Length of the argument in characters
+    pub length: usize,
+    /// Bytes of the source file the argument covers, set once the decorator has been placed in the file
+    pub span: Span,
+}
+
+impl fmt::Display for DecoratorArgument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name}={}", self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// The arguments passed to a decorator in the order they were written
+#[derive(Debug, Clone, Default)]
+pub struct DecoratorArguments {
+    pub list: Vec<DecoratorArgument>,
+}
+
+impl DecoratorArguments {
+    pub fn new(list: Vec<DecoratorArgument>) -> Self {
+        DecoratorArguments { list }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns the positional arguments along with their index in the argument list
+    pub fn positional(&self) -> Vec<(usize, &DecoratorValue)> {
+        self.list
+            .iter()
+            .enumerate()
+            .filter(|(_, arg)| arg.name.is_none())
+            .map(|(i, arg)| (i, &arg.value))
+            .collect()
+    }
+
+    /// Returns the positional argument at the provided position
+    pub fn get(&self, position: usize) -> Option<(usize, &DecoratorValue)> {
+        self.positional().into_iter().nth(position)
+    }
+
+    /// Returns the named argument along with its index in the argument list
+    pub fn named(&self, name: &str) -> Option<(usize, &DecoratorValue)> {
+        self.list
+            .iter()
+            .enumerate()
+            .find(|(_, arg)| arg.name.as_deref() == Some(name))
+            .map(|(i, arg)| (i, &arg.value))
+    }
+}
+
+impl fmt::Display for DecoratorArguments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arguments: Vec<String> = self.list.iter().map(|a| a.to_string()).collect();
+
+        write!(f, "{}", arguments.join(", "))
+    }
+}
+
+/// An error in the syntax of the decorator arguments
+#[derive(Debug, Clone)]
+pub struct SyntaxError {
+    pub message: String,
+    /// Offset of the error in characters from the start of the decorator (after the `@`)
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl SyntaxError {
+    pub fn new(message: &str, offset: usize, length: usize) -> Self {
+        SyntaxError {
+            message: message.to_owned(),
+            offset,
+            length: length.max(1),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+    offset: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.index += 1;
+        }
+    }
+
+    fn error(&self, message: &str, start: usize, length: usize) -> SyntaxError {
+        SyntaxError::new(message, self.offset + start, length)
+    }
+
+    fn parse_value(&mut self) -> Result<DecoratorValue, SyntaxError> {
+        self.skip_whitespace();
+
+        let start = self.index;
+
+        match self.peek() {
+            Some('"') => {
+                self.index += 1;
+
+                let mut value = String::new();
+
+                loop {
+                    match self.peek() {
+                        Some('"') => {
+                            self.index += 1;
+                            return Ok(DecoratorValue::String(value));
+                        }
+                        // Only escaped quotes are unescaped so that regex patterns are kept as written
+                        Some('\\') if self.chars.get(self.index + 1) == Some(&'"') => {
+                            value.push('"');
+                            self.index += 2;
+                        }
+                        Some(c) => {
+                            value.push(c);
+                            self.index += 1;
+                        }
+                        None => {
+                            return Err(self.error(
+                                "Unterminated string. Expected a closing '\"'.",
+                                start,
+                                self.index - start,
+                            ))
+                        }
+                    }
+                }
+            }
+            Some('[') => {
+                self.index += 1;
+
+                let mut values: Vec<DecoratorValue> = Vec::new();
+
+                loop {
+                    self.skip_whitespace();
+
+                    match self.peek() {
+                        Some(']') => {
+                            self.index += 1;
+                            return Ok(DecoratorValue::List(values));
+                        }
+                        Some(_) => {
+                            values.push(self.parse_value()?);
+
+                            self.skip_whitespace();
+
+                            match self.peek() {
+                                Some(',') => self.index += 1,
+                                Some(']') => {}
+                                None => {
+                                    return Err(self.error(
+                                        "Unterminated list. Expected a closing ']'.",
+                                        start,
+                                        self.index - start,
+                                    ))
+                                }
+                                _ => {
+                                    return Err(self.error(
+                                        "Expected ',' or ']' after a list item.",
+                                        self.index,
+                                        1,
+                                    ))
+                                }
+                            }
+                        }
+                        None => {
+                            return Err(self.error(
+                                "Unterminated list. Expected a closing ']'.",
+                                start,
+                                self.index - start,
+                            ))
+                        }
+                    }
+                }
+            }
+            _ => {
+                let mut token = String::new();
+
+                while let Some(c) = self.peek() {
+                    if c == ',' || c == ']' || c == '=' {
+                        break;
+                    }
+                    token.push(c);
+                    self.index += 1;
+                }
+
+                let token = token.trim();
+
+                if token.is_empty() {
+                    return Err(self.error("Expected a value.", start, 1));
+                }
+
+                Ok(DecoratorValue::from_str(token))
+            }
+        }
+    }
+
+    /// Reads the name of a named argument if the next argument is named
+    fn parse_name(&mut self) -> Option<String> {
+        self.skip_whitespace();
+
+        let start = self.index;
+        let mut name = String::new();
+
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '_' {
+                name.push(c);
+                self.index += 1;
+            } else {
+                break;
+            }
+        }
+
+        self.skip_whitespace();
+
+        if !name.is_empty() && self.peek() == Some('=') {
+            self.index += 1;
+            return Some(name);
+        }
+
+        self.index = start;
+
+        None
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<DecoratorArgument>, SyntaxError> {
+        let mut arguments: Vec<DecoratorArgument> = Vec::new();
+
+        self.skip_whitespace();
+
+        if self.peek().is_none() {
+            return Ok(arguments);
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            let start = self.index;
+
+            let name = self.parse_name();
+
+            if let Some(name) = &name {
+                if arguments.iter().any(|a| a.name.as_ref() == Some(name)) {
+                    return Err(self.error(
+                        &format!("The argument '{name}' was provided more than once."),
+                        start,
+                        name.chars().count(),
+                    ));
+                }
+            } else if arguments.iter().any(|a| a.name.is_some()) {
+                return Err(self.error(
+                    "Positional arguments must come before named arguments.",
+                    start,
+                    1,
+                ));
+            }
+
+            let value = self.parse_value()?;
+
+            let mut end = self.index;
+            while end > start && self.chars[end - 1].is_whitespace() {
+                end -= 1;
+            }
+
+            arguments.push(DecoratorArgument {
+                name,
+                value,
+                offset: self.offset + start,
+                length: end - start,
+                span: Span::default(),
+            });
+
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => {
+                    self.index += 1;
+                    self.skip_whitespace();
+
+                    // Allow a trailing comma
+                    if self.peek().is_none() {
+                        return Ok(arguments);
+                    }
+                }
+                None => return Ok(arguments),
+                Some(c) => {
+                    return Err(self.error(&format!("Unexpected '{c}'."), self.index, 1));
+                }
+            }
+        }
+    }
+}
+
+/// Parses the arguments between the parentheses of a decorator
+///
+/// # Parameters
+/// - `input`: The text between the parentheses
+/// - `offset`: The offset of the text from the start of the decorator, used for positions
+///
+/// # Examples
+/// ```
+/// use vnv::decorators::arguments;
+///
+/// let arguments = arguments::parse("1, max=10", 0).unwrap();
+///
+/// assert_eq!(arguments.len(), 2);
+/// assert_eq!(arguments.named("max").unwrap().0, 1);
+/// ```
+pub fn parse(input: &str, offset: usize) -> Result<DecoratorArguments, SyntaxError> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        index: 0,
+        offset,
+    };
+
+    Ok(DecoratorArguments::new(parser.parse_arguments()?))
+}
@@ -0,0 +1,431 @@
+use crate::decorators::{
+    compare_number, DecoratorArguments, DecoratorParseResult, DecoratorValidationResult,
+    DecoratorValue, ValidationError,
+};
+use crate::parsing::{Environment, Key, ValueType};
+use crate::util::CompareResult;
+use std::collections::HashMap;
+
+pub type CrossKeyValidator =
+    Box<dyn Fn(&Key, &DecoratorArguments, &KeySet) -> DecoratorValidationResult>;
+
+/// The keys a cross-key decorator is validated against
+pub struct KeySet<'a> {
+    /// Every key in the file, used to tell if a referenced key is defined
+    pub all: &'a [Key],
+    /// The resolved keys of the environment being validated, keys skipped by `@when` are left out
+    pub active: &'a [Key],
+}
+
+/// A decorator that validates a key against the values of other keys
+///
+/// These are run in a second pass once every key in the file has been parsed.
+pub struct CrossKeyDecorator {
+    pub name: String,
+    pub validator: CrossKeyValidator,
+}
+
+impl CrossKeyDecorator {
+    pub fn new(name: &str, validator: CrossKeyValidator) -> CrossKeyDecorator {
+        CrossKeyDecorator {
+            name: name.to_owned(),
+            validator,
+        }
+    }
+}
+
+/// Finds the keys with the provided name that share an environment with the key
+///
+/// A key in all environments is related to keys in every environment so
+/// it may return more than one key.
+pub fn related<'a>(key: &Key, name: &str, keys: &'a [Key]) -> Vec<&'a Key> {
+    keys.iter()
+        .filter(|k| {
+            k.key == name
+                && (k.environment == key.environment
+                    || k.environment == Environment::All
+                    || key.environment == Environment::All)
+        })
+        .collect()
+}
+
+/// Returns true if the value is not empty
+pub fn has_value(value: &ValueType) -> bool {
+    match value {
+        ValueType::Integer(_) | ValueType::Number(_) => true,
+        ValueType::String(v) => !v.is_empty(),
+        ValueType::StringArray(v) => !v.is_empty(),
+        ValueType::IntegerArray(v) => !v.is_empty(),
+        ValueType::NumberArray(v) => !v.is_empty(),
+    }
+}
+
+/// Returns true if the value is equal to the value passed to the decorator
+pub fn value_equals(value: &ValueType, expected: &DecoratorValue) -> bool {
+    if let Some(result) = compare_number(value, expected) {
+        return result == CompareResult::Equal;
+    }
+
+    match (value, expected) {
+        (ValueType::String(v), DecoratorValue::String(e)) => v == e,
+        (ValueType::Integer(_) | ValueType::Number(_), DecoratorValue::String(e)) => {
+            value.to_string() == *e
+        }
+        (ValueType::String(v), DecoratorValue::Integer(_) | DecoratorValue::Float(_)) => {
+            *v == expected.to_string()
+        }
+        (ValueType::String(v), DecoratorValue::Bool(e)) => *v == e.to_string(),
+        _ => false,
+    }
+}
+
+/// Returns the length of the value (the number of items for arrays)
+fn length(value: &ValueType) -> Option<usize> {
+    match value {
+        ValueType::Integer(_) | ValueType::Number(_) => None,
+        ValueType::String(v) => Some(v.len()),
+        ValueType::StringArray(v) => Some(v.len()),
+        ValueType::IntegerArray(v) => Some(v.len()),
+        ValueType::NumberArray(v) => Some(v.len()),
+    }
+}
+
+/// Gets the name of the key referenced by the first argument of the decorator
+fn referenced_key(name: &str, arguments: &DecoratorArguments) -> Result<String, ValidationError> {
+    match arguments.get(0) {
+        Some((_, DecoratorValue::String(v))) => Ok(v.to_owned()),
+        Some((i, v)) => {
+            let error_message = format!("'{}' is not a valid key name for {name}.", v.raw());
+
+            Err(ValidationError::new(&error_message, None).at_argument(i))
+        }
+        None => {
+            let error_message = format!(
+                "The {name} decorator requires the name of a key to be provided with it. Ex: `@{name}(\"OTHER_KEY\")`"
+            );
+
+            Err(ValidationError::new(&error_message, None))
+        }
+    }
+}
+
+/// Returns the names of the keys the decorator references
+///
+/// Decorators that don't validate against other keys reference none.
+pub fn references(decorator: &DecoratorParseResult) -> Vec<String> {
+    match decorator.key.as_str() {
+        "gt" | "gte" | "lt" | "lte" | "requiredIf" | "when" | "sameLengthAs" => {
+            return referenced_key(&decorator.key, &decorator.arguments)
+                .into_iter()
+                .collect();
+        }
+        "oneOfKeys" => {
+            return decorator
+                .arguments
+                .positional()
+                .into_iter()
+                .filter_map(|(_, v)| match v {
+                    DecoratorValue::String(name) => Some(name.to_owned()),
+                    _ => None,
+                })
+                .collect();
+        }
+        _ => return Vec::new(),
+    }
+}
+
+/// Creates a decorator that compares a number to the value of another key
+fn comparison(
+    name: &'static str,
+    description: &'static str,
+    passes: fn(CompareResult) -> bool,
+) -> CrossKeyDecorator {
+    CrossKeyDecorator::new(
+        name,
+        Box::new(move |key, arguments, keys| {
+            let other_name = match referenced_key(name, arguments) {
+                Ok(other_name) => other_name,
+                Err(err) => return DecoratorValidationResult::Error(vec![err]),
+            };
+
+            if !matches!(key.value, ValueType::Integer(_) | ValueType::Number(_)) {
+                let error_message = format!(
+                    "{name} does not support this variable type. {name} only supports the number type."
+                );
+
+                return DecoratorValidationResult::Error(vec![ValidationError::new(
+                    &error_message,
+                    None,
+                )]);
+            }
+
+            if related(key, &other_name, keys.all).is_empty() {
+                let error_message = format!("'{other_name}' is not defined.");
+
+                return DecoratorValidationResult::Error(vec![ValidationError::new(
+                    &error_message,
+                    None,
+                )]);
+            }
+
+            let mut errors: Vec<ValidationError> = Vec::new();
+
+            // Nothing is compared when the other key is skipped in the environment
+            for other in related(key, &other_name, keys.active) {
+                match key.value.compare(&other.value) {
+                    Some(result) => {
+                        if !passes(result) {
+                            let error_message = format!(
+                                "{} must be {description} {other_name} ({}).",
+                                key.value, other.value
+                            );
+
+                            errors.push(ValidationError::new(
+                                &error_message,
+                                Some(key.value.to_owned()),
+                            ));
+                        }
+                    }
+                    None => {
+                        let error_message = format!(
+                            "'{other_name}' must be a number to be compared with {name}."
+                        );
+
+                        errors.push(ValidationError::new(&error_message, None));
+                    }
+                }
+            }
+
+            if !errors.is_empty() {
+                return DecoratorValidationResult::Error(errors);
+            } else {
+                return DecoratorValidationResult::Ok;
+            }
+        }),
+    )
+}
+
+pub fn get() -> HashMap<String, CrossKeyDecorator> {
+    let mut map: HashMap<String, CrossKeyDecorator> = HashMap::new();
+
+    let decorators = vec![
+        // ====== gt / gte / lt / lte ======
+        // Compares the number to the value of another key
+        comparison("gt", "greater than", |r| matches!(r, CompareResult::Greater)),
+        comparison("gte", "greater than or equal to", |r| {
+            !matches!(r, CompareResult::Less)
+        }),
+        comparison("lt", "less than", |r| matches!(r, CompareResult::Less)),
+        comparison("lte", "less than or equal to", |r| {
+            !matches!(r, CompareResult::Greater)
+        }),
+        // ====== requiredIf ======
+        // Requires the key to have a value when another key is equal to the provided value
+        CrossKeyDecorator::new(
+            "requiredIf",
+            Box::new(|key, arguments, keys| {
+                let other_name = match referenced_key("requiredIf", arguments) {
+                    Ok(other_name) => other_name,
+                    Err(err) => return DecoratorValidationResult::Error(vec![err]),
+                };
+
+                let expected = match arguments.get(1) {
+                    Some((_, expected)) => expected,
+                    None => {
+                        let error_message = "The requiredIf decorator requires a key and a value to be provided with it. Ex: `@requiredIf(\"TLS_ENABLED\", true)`";
+
+                        return DecoratorValidationResult::Error(vec![ValidationError::new(
+                            error_message,
+                            None,
+                        )]);
+                    }
+                };
+
+                if has_value(&key.value) {
+                    return DecoratorValidationResult::Ok;
+                }
+
+                let required = related(key, &other_name, keys.active)
+                    .iter()
+                    .any(|other| value_equals(&other.value, expected));
+
+                if required {
+                    let error_message = format!(
+                        "'{}' is required when '{other_name}' is {}.",
+                        key.key,
+                        expected.raw()
+                    );
+
+                    return DecoratorValidationResult::Error(vec![ValidationError::new(
+                        &error_message,
+                        None,
+                    )]);
+                }
+
+                return DecoratorValidationResult::Ok;
+            }),
+        ),
+        // ====== when ======
+        // Only includes the key when another key is equal to the provided value, see `ParseResult::resolve`
+        CrossKeyDecorator::new(
+            "when",
+            Box::new(|key, arguments, keys| {
+                let other_name = match referenced_key("when", arguments) {
+                    Ok(other_name) => other_name,
+                    Err(err) => return DecoratorValidationResult::Error(vec![err]),
+                };
+
+                if arguments.get(1).is_none() {
+                    let error_message = "The when decorator requires a key and a value to be provided with it. Ex: `@when(\"AUTH_PROVIDER\", \"okta\")`";
+
+                    return DecoratorValidationResult::Error(vec![ValidationError::new(
+                        error_message,
+                        None,
+                    )]);
+                }
+
+                // Reported at the argument since the key is never included to show its errors
+                if other_name == key.key {
+                    let error_message = format!("'{other_name}' can't be a condition of itself.");
+
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(&error_message, None).at_argument(0),
+                    ]);
+                }
+
+                if !keys.all.iter().any(|k| k.key == other_name) {
+                    let error_message = format!("'{other_name}' is not defined.");
+
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(&error_message, None).at_argument(0),
+                    ]);
+                }
+
+                return DecoratorValidationResult::Ok;
+            }),
+        ),
+        // ====== oneOfKeys ======
+        // Requires that exactly one of the key and the provided keys has a value
+        CrossKeyDecorator::new(
+            "oneOfKeys",
+            Box::new(|key, arguments, keys| {
+                let mut names: Vec<String> = Vec::new();
+
+                for (i, arg) in arguments.positional() {
+                    match arg {
+                        DecoratorValue::String(v) => names.push(v.to_owned()),
+                        _ => {
+                            let error_message = format!("'{}' is not a valid key name for oneOfKeys.", arg.raw());
+
+                            return DecoratorValidationResult::Error(vec![
+                                ValidationError::new(&error_message, None).at_argument(i),
+                            ]);
+                        }
+                    }
+                }
+
+                if names.is_empty() {
+                    let error_message = "The oneOfKeys decorator requires the names of the other keys to be provided with it. Ex: `@oneOfKeys(\"DATABASE_URL\")`";
+
+                    return DecoratorValidationResult::Error(vec![ValidationError::new(
+                        error_message,
+                        None,
+                    )]);
+                }
+
+                let mut with_value: Vec<String> = Vec::new();
+
+                if has_value(&key.value) {
+                    with_value.push(key.key.to_owned());
+                }
+
+                for name in &names {
+                    if related(key, name, keys.active).iter().any(|k| has_value(&k.value)) {
+                        with_value.push(name.to_owned());
+                    }
+                }
+
+                if with_value.len() == 1 {
+                    return DecoratorValidationResult::Ok;
+                }
+
+                let mut all = vec![key.key.to_owned()];
+                all.extend(names);
+
+                let error_message = if with_value.is_empty() {
+                    format!("Exactly one of '{}' must have a value but none do.", all.join("', '"))
+                } else {
+                    format!(
+                        "Exactly one of '{}' must have a value but '{}' do.",
+                        all.join("', '"),
+                        with_value.join("', '")
+                    )
+                };
+
+                return DecoratorValidationResult::Error(vec![ValidationError::new(
+                    &error_message,
+                    None,
+                )]);
+            }),
+        ),
+        // ====== sameLengthAs ======
+        // Requires the key to have the same number of items (or characters for strings) as another key
+        CrossKeyDecorator::new(
+            "sameLengthAs",
+            Box::new(|key, arguments, keys| {
+                let other_name = match referenced_key("sameLengthAs", arguments) {
+                    Ok(other_name) => other_name,
+                    Err(err) => return DecoratorValidationResult::Error(vec![err]),
+                };
+
+                let len = match length(&key.value) {
+                    Some(len) => len,
+                    None => {
+                        let error_message = "sameLengthAs does not support this variable type. sameLengthAs only supports the string, string array and number array types.";
+
+                        return DecoratorValidationResult::Error(vec![ValidationError::new(
+                            error_message,
+                            None,
+                        )]);
+                    }
+                };
+
+                if related(key, &other_name, keys.all).is_empty() {
+                    let error_message = format!("'{other_name}' is not defined.");
+
+                    return DecoratorValidationResult::Error(vec![ValidationError::new(
+                        &error_message,
+                        None,
+                    )]);
+                }
+
+                let mut errors: Vec<ValidationError> = Vec::new();
+
+                for other in related(key, &other_name, keys.active) {
+                    if let Some(other_len) = length(&other.value) {
+                        if other_len != len {
+                            let error_message = format!(
+                                "'{}' has a length of {len} but '{other_name}' has a length of {other_len}.",
+                                key.key
+                            );
+
+                            errors.push(ValidationError::new(&error_message, None));
+                        }
+                    }
+                }
+
+                if !errors.is_empty() {
+                    return DecoratorValidationResult::Error(errors);
+                } else {
+                    return DecoratorValidationResult::Ok;
+                }
+            }),
+        ),
+    ];
+
+    for dec in decorators {
+        map.insert(dec.name.to_owned(), dec);
+    }
+
+    map
+}
@@ -1,35 +1,134 @@
-use crate::util::{trim_quotes, Compare};
-use crate::{parsing::ValueType, util::CompareResult};
-use regex::Regex;
+use crate::util::{closest_match, secrets, trim_quotes, Compare};
+use crate::{
+    diagnostics::{LineIndex, Span},
+    parsing::{naming, units::Unit, FilePosition, ValueType},
+    util::CompareResult,
+};
+use regex::{Regex, RegexBuilder};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+pub mod arguments;
+pub mod cross_key;
+
+pub use arguments::{DecoratorArguments, SyntaxError};
 
 #[derive(Debug, Clone)]
 pub enum DecoratorValue {
     String(String),
-    Integer(f64),
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    List(Vec<DecoratorValue>),
     None,
 }
 
 impl DecoratorValue {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(val: &str) -> Self {
-        if val.parse::<f64>().is_ok() {
-            return DecoratorValue::Integer(val.parse::<f64>().unwrap());
+        if let Ok(v) = val.parse::<i64>() {
+            return DecoratorValue::Integer(v);
+        }
+
+        if let Ok(v) = val.parse::<f64>() {
+            return DecoratorValue::Float(v);
+        }
+
+        if let Ok(b) = val.parse::<bool>() {
+            return DecoratorValue::Bool(b);
         }
 
         return DecoratorValue::String(trim_quotes(val));
     }
+
+    /// Returns the value as a float if it is a number
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DecoratorValue::Integer(v) => Some(*v as f64),
+            DecoratorValue::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as it should be shown in messages (strings are not quoted)
+    pub fn raw(&self) -> String {
+        match self {
+            DecoratorValue::String(v) => v.to_owned(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for DecoratorValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecoratorValue::String(v) => write!(f, "\"{v}\""),
+            DecoratorValue::Integer(v) => write!(f, "{v}"),
+            DecoratorValue::Float(v) => write!(f, "{v}"),
+            DecoratorValue::Bool(v) => write!(f, "{v}"),
+            DecoratorValue::List(v) => {
+                let items: Vec<String> = v.iter().map(|i| i.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            DecoratorValue::None => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct DecoratorParseResult {
     pub key: String,
-    pub value: DecoratorValue,
+    pub arguments: DecoratorArguments,
+    /// Position of the `@` in the source file
+    pub position: FilePosition,
+    /// Bytes of the source file the decorator covers including the `@`
+    pub span: Span,
+}
+
+impl DecoratorParseResult {
+    /// Places the decorator and its arguments in the source file
+    ///
+    /// `length` is the number of characters of the decorator after the `@`.
+    pub fn locate(&mut self, index: &LineIndex, position: FilePosition, length: usize) {
+        self.position = position;
+        self.span = index.span(position, length as u32 + 1);
+
+        for arg in self.arguments.list.iter_mut() {
+            let start = FilePosition {
+                line: position.line,
+                column: position.column + 1 + arg.offset as u32,
+            };
+
+            arg.span = index.span(start, arg.length.max(1) as u32);
+        }
+    }
+
+    /// Gets the span of the argument at the provided index in the source file
+    pub fn argument_span(&self, index: usize) -> Option<Span> {
+        self.arguments.list.get(index).map(|arg| arg.span)
+    }
+}
+
+impl fmt::Display for DecoratorParseResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.arguments.is_empty() {
+            return write!(f, "@{}", self.key);
+        }
+
+        write!(f, "@{}({})", self.key, self.arguments)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ValidationError {
     pub message: String,
     pub value: Option<ValueType>,
+    /// The index of the decorator argument that caused the error
+    pub argument: Option<usize>,
+    /// The index of the array element that caused the error
+    pub item: Option<usize>,
 }
 
 impl ValidationError {
@@ -37,8 +136,22 @@ impl ValidationError {
         ValidationError {
             message: mes.to_owned(),
             value: value.to_owned(),
+            argument: None,
+            item: None,
         }
     }
+
+    /// Marks the error as caused by the decorator argument at the provided index
+    pub fn at_argument(mut self, index: usize) -> Self {
+        self.argument = Some(index);
+        self
+    }
+
+    /// Marks the error as caused by the array element at the provided index
+    pub fn at_item(mut self, index: usize) -> Self {
+        self.item = Some(index);
+        self
+    }
 }
 
 pub enum DecoratorValidationResult {
@@ -46,26 +159,136 @@ pub enum DecoratorValidationResult {
     Error(Vec<ValidationError>),
 }
 
+pub type Validator = Box<dyn Fn(ValueType, &DecoratorArguments) -> DecoratorValidationResult>;
+
 pub struct Decorator {
     pub name: String,
-    pub validator: Box<dyn Fn(ValueType, DecoratorValue) -> DecoratorValidationResult>,
+    pub validator: Validator,
+    /// True if the decorator validates each item of an array and accepts the `each` argument
+    pub each: bool,
+    /// Validates arrays as a whole when `each=false` is provided
+    pub whole: Option<Validator>,
 }
 
 impl Decorator {
+    /// Creates a decorator that accepts a single positional value
     pub fn new(
         name: &str,
         validator: Box<dyn Fn(ValueType, DecoratorValue) -> DecoratorValidationResult>,
     ) -> Decorator {
+        Decorator::with_arguments(name, single_value(name, validator))
+    }
+
+    /// Creates a decorator that accepts any number of positional and named arguments
+    pub fn with_arguments(name: &str, validator: Validator) -> Decorator {
         Decorator {
             name: name.to_owned(),
-            validator: validator,
+            validator,
+            each: true,
+            whole: None,
+        }
+    }
+
+    /// Marks the decorator as validating arrays as a whole so it doesn't accept the `each` argument
+    pub fn validates_whole(mut self) -> Decorator {
+        self.each = false;
+        self
+    }
+
+    /// Sets the validator used for arrays when `each=false` is provided
+    pub fn with_whole(mut self, validator: Validator) -> Decorator {
+        self.whole = Some(validator);
+        self
+    }
+
+    /// Validates the value with the provided arguments
+    ///
+    /// Arrays are validated item by item unless `each=false` is provided. In that case
+    /// the array is validated as a whole by the `whole` validator. Decorators without
+    /// one don't accept `each=false`.
+    pub fn validate(&self, value: ValueType, arguments: &DecoratorArguments) -> DecoratorValidationResult {
+        let (each_index, each) = match arguments.named("each") {
+            Some((i, _)) if !self.each => {
+                let error_message = format!(
+                    "The {} decorator does not accept the named argument 'each'.",
+                    self.name
+                );
+
+                return DecoratorValidationResult::Error(vec![
+                    ValidationError::new(&error_message, None).at_argument(i),
+                ]);
+            }
+            Some((i, DecoratorValue::Bool(each))) => (i, *each),
+            Some((i, v)) => {
+                let error_message = format!(
+                    "'{}' is not valid for 'each'. 'each' requires a boolean value.",
+                    v.raw()
+                );
+
+                return DecoratorValidationResult::Error(vec![
+                    ValidationError::new(&error_message, None).at_argument(i),
+                ]);
+            }
+            None => return (self.validator)(value, arguments),
+        };
+
+        // Removes `each` from the arguments while keeping track of the original indexes
+        let indexes: Vec<usize> = (0..arguments.len())
+            .filter(|i| arguments.list[*i].name.as_deref() != Some("each"))
+            .collect();
+        let remaining = DecoratorArguments::new(
+            indexes.iter().map(|i| arguments.list[*i].to_owned()).collect(),
+        );
+
+        let validator = match (&self.whole, each) {
+            (_, true) => &self.validator,
+            (Some(whole), false) if item_count(&value).is_some() => whole,
+            (Some(_), false) => &self.validator,
+            (None, false) => {
+                let error_message = format!(
+                    "The {} decorator can only validate the items of an array. 'each' can't be false.",
+                    self.name
+                );
+
+                return DecoratorValidationResult::Error(vec![
+                    ValidationError::new(&error_message, None).at_argument(each_index),
+                ]);
+            }
+        };
+
+        match validator(value, &remaining) {
+            DecoratorValidationResult::Error(errors) => DecoratorValidationResult::Error(
+                errors
+                    .into_iter()
+                    .map(|mut err| {
+                        err.argument = err.argument.and_then(|i| indexes.get(i).copied());
+                        err
+                    })
+                    .collect(),
+            ),
+            DecoratorValidationResult::Ok => DecoratorValidationResult::Ok,
         }
     }
 }
 
+/// The decorators that document a key instead of validating it
+pub const DOCUMENTATION: [&str; 4] = ["description", "example", "deprecated", "owner"];
+
+/// Creates the error for an argument that has the wrong type
+pub fn invalid_argument(name: &str, value: &DecoratorValue, expected: &str) -> ValidationError {
+    let error_message = format!(
+        "'{}' is not valid for decorator type '{name}'. '{name}' requires {expected} value.",
+        value.raw()
+    );
+
+    ValidationError::new(&error_message, None).at_argument(0)
+}
+
 pub fn get() -> HashMap<String, Decorator> {
     let mut map: HashMap<String, Decorator> = HashMap::new();
 
+    let cache: RegexCache = Rc::new(RefCell::new(HashMap::new()));
+
     let decorators = vec![
         // ====== Private ======
         // This will modify the scope of the .env variable to private
@@ -91,99 +314,184 @@ pub fn get() -> HashMap<String, Decorator> {
             "prod",
             Box::new(|_value, _decorator_value| DecoratorValidationResult::Ok),
         ),
+        // ====== Sensitive ======
+        // Hides the value of the key in the output even when cloak is disabled
+        Decorator::new(
+            "sensitive",
+            Box::new(|_value, _decorator_value| DecoratorValidationResult::Ok),
+        ),
+        // ====== description / example / deprecated / owner ======
+        // Documents the key for `vnv docs` without validating the value
+        documentation("description", "The port the server listens on"),
+        documentation("example", "3000"),
+        documentation("deprecated", "Use DATABASE_URL instead"),
+        documentation("owner", "platform-team"),
+        // ====== alias ======
+        // The previous name of a renamed key. Builds can write the key under both names during a migration.
+        Decorator::new(
+            "alias",
+            Box::new(|_value, decorator_value| match decorator_value {
+                DecoratorValue::String(name) => {
+                    if naming::is_posix(&name) {
+                        return DecoratorValidationResult::Ok;
+                    }
+
+                    let error_message = format!("'{name}' is not a valid key to use as an alias.");
+
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(&error_message, None).at_argument(0),
+                    ]);
+                }
+                DecoratorValue::None => {
+                    let error_message = "The alias decorator requires a value to be provided with it. Ex: `@alias(\"DB_URL\")`";
+
+                    return DecoratorValidationResult::Error(vec![ValidationError::new(
+                        error_message,
+                        None,
+                    )]);
+                }
+                _ => {
+                    return DecoratorValidationResult::Error(vec![invalid_argument(
+                        "alias",
+                        &decorator_value,
+                        "a string",
+                    )]);
+                }
+            }),
+        )
+        .validates_whole(),
         // ====== min ======
         // min compares the decorator value to the length of the string or the size of the number
         // If the value is a string or number array each value in the array is compared
+        // With `each=false` the decorator value is compared to the number of items in the array
         Decorator::new(
             "min",
             Box::new(
                 |value: ValueType, decorator_value: DecoratorValue| match decorator_value {
-                    DecoratorValue::String(v) => {
-                        let error_message = format!("'{v}' is not valid for decorator type 'min'. 'min' requires a number value.");
-
-                        return DecoratorValidationResult::Error(vec![ValidationError::new(
-                            &error_message,
-                            None,
+                    DecoratorValue::String(_) | DecoratorValue::Bool(_) | DecoratorValue::List(_) => {
+                        return DecoratorValidationResult::Error(vec![invalid_argument(
+                            "min",
+                            &decorator_value,
+                            "a number",
                         )]);
                     }
-                    DecoratorValue::Integer(dec_value) => match value {
-                        ValueType::Number(v) => match v.cmp(&dec_value) {
-                            CompareResult::Less => {
-                                let error_message =
-                                    format!("{v} is too small. Minimum value is {dec_value}.");
-
-                                return DecoratorValidationResult::Error(vec![
-                                    ValidationError::new(
-                                        &error_message,
-                                        Some(ValueType::Number(v)),
-                                    ),
-                                ]);
-                            }
-                            _ => return DecoratorValidationResult::Ok,
-                        },
-                        ValueType::String(v) => {
-                            if dec_value > v.len() as f64 {
-                                let error_message =
-                                    format!("'{v}' is too short. Minimum length is {dec_value}.");
-
-                                return DecoratorValidationResult::Error(vec![
-                                    ValidationError::new(
-                                        &error_message,
-                                        Some(ValueType::String(v)),
-                                    ),
-                                ]);
-                            }
+                    DecoratorValue::Integer(_) | DecoratorValue::Float(_) => {
+                        let dec_value = decorator_value.as_f64().unwrap_or_default();
 
-                            return DecoratorValidationResult::Ok;
-                        }
-                        ValueType::StringArray(v) => {
-                            let mut errors: Vec<ValidationError> = Vec::new();
-                            for s in v {
-                                if dec_value > s.len() as f64 {
-                                    let error_message = format!(
-                                        "'{s}' is too short. Minimum length is {dec_value}."
-                                    );
-
-                                    errors.push(ValidationError::new(
-                                        &error_message,
-                                        Some(ValueType::String(s)),
-                                    ));
+                        match value {
+                            ValueType::Integer(v) => match Compare::cmp(&v, &dec_value) {
+                                CompareResult::Less => {
+                                    let error_message =
+                                        format!("{v} is too small. Minimum value is {dec_value}.");
+
+                                    return DecoratorValidationResult::Error(vec![
+                                        ValidationError::new(
+                                            &error_message,
+                                            Some(ValueType::Integer(v)),
+                                        ),
+                                    ]);
+                                }
+                                _ => return DecoratorValidationResult::Ok,
+                            },
+                            ValueType::Number(v) => match v.cmp(&dec_value) {
+                                CompareResult::Less => {
+                                    let error_message =
+                                        format!("{v} is too small. Minimum value is {dec_value}.");
+
+                                    return DecoratorValidationResult::Error(vec![
+                                        ValidationError::new(
+                                            &error_message,
+                                            Some(ValueType::Number(v)),
+                                        ),
+                                    ]);
+                                }
+                                _ => return DecoratorValidationResult::Ok,
+                            },
+                            ValueType::String(v) => {
+                                if dec_value > v.len() as f64 {
+                                    let error_message =
+                                        format!("'{v}' is too short. Minimum length is {dec_value}.");
+
+                                    return DecoratorValidationResult::Error(vec![
+                                        ValidationError::new(
+                                            &error_message,
+                                            Some(ValueType::String(v)),
+                                        ),
+                                    ]);
                                 }
-                            }
 
-                            if errors.len() != 0 {
-                                return DecoratorValidationResult::Error(errors);
-                            } else {
                                 return DecoratorValidationResult::Ok;
                             }
-                        }
-                        ValueType::NumberArray(v) => {
-                            let mut errors: Vec<ValidationError> = Vec::new();
-                            for num in v {
-                                match num.cmp(&dec_value) {
-                                    CompareResult::Less => {
+                            ValueType::StringArray(v) => {
+                                let mut errors: Vec<ValidationError> = Vec::new();
+                                for s in v {
+                                    if dec_value > s.len() as f64 {
+                                        let error_message = format!(
+                                            "'{s}' is too short. Minimum length is {dec_value}."
+                                        );
+
+                                        errors.push(ValidationError::new(
+                                            &error_message,
+                                            Some(ValueType::String(s)),
+                                        ));
+                                    }
+                                }
+
+                                if !errors.is_empty() {
+                                    return DecoratorValidationResult::Error(errors);
+                                } else {
+                                    return DecoratorValidationResult::Ok;
+                                }
+                            }
+                            ValueType::IntegerArray(v) => {
+                                let mut errors: Vec<ValidationError> = Vec::new();
+                                for num in v {
+                                    if Compare::cmp(&num, &dec_value) == CompareResult::Less {
                                         let error_message = format!(
                                             "{num} is too small. Minimum value is {dec_value}."
                                         );
 
                                         errors.push(ValidationError::new(
                                             &error_message,
-                                            Some(ValueType::Number(num)),
+                                            Some(ValueType::Integer(num)),
                                         ));
                                     }
-                                    _ => continue,
+                                }
+
+                                if !errors.is_empty() {
+                                    return DecoratorValidationResult::Error(errors);
+                                } else {
+                                    return DecoratorValidationResult::Ok;
                                 }
                             }
+                            ValueType::NumberArray(v) => {
+                                let mut errors: Vec<ValidationError> = Vec::new();
+                                for num in v {
+                                    match num.cmp(&dec_value) {
+                                        CompareResult::Less => {
+                                            let error_message = format!(
+                                                "{num} is too small. Minimum value is {dec_value}."
+                                            );
+
+                                            errors.push(ValidationError::new(
+                                                &error_message,
+                                                Some(ValueType::Number(num)),
+                                            ));
+                                        }
+                                        _ => continue,
+                                    }
+                                }
 
-                            if errors.len() != 0 {
-                                return DecoratorValidationResult::Error(errors);
-                            } else {
-                                return DecoratorValidationResult::Ok;
+                                if !errors.is_empty() {
+                                    return DecoratorValidationResult::Error(errors);
+                                } else {
+                                    return DecoratorValidationResult::Ok;
+                                }
                             }
-                        }
+                    }
                     },
                     DecoratorValue::None => {
-                        let error_message = format!("The min decorator requires a value to be provided with it. Ex: `@min(5)`");
+                        let error_message = "The min decorator requires a value to be provided with it. Ex: `@min(5)`".to_string();
 
                         return DecoratorValidationResult::Error(vec![ValidationError::new(
                             &error_message,
@@ -192,102 +500,140 @@ pub fn get() -> HashMap<String, Decorator> {
                     }
                 },
             ),
-        ),
+        )
+        .with_whole(item_bound("min", "@min(5)", true)),
         // ====== max ======
         // max compares the decorator value to the length of the string or the size of the number
         // If the value is a string or number array each value in the array is compared
+        // With `each=false` the decorator value is compared to the number of items in the array
         Decorator::new(
             "max",
             Box::new(
                 |value: ValueType, decorator_value: DecoratorValue| match decorator_value {
-                    DecoratorValue::String(v) => {
-                        let error_message = format!("'{v}' is not valid for decorator type 'max'. 'max' requires a number value.");
-
-                        return DecoratorValidationResult::Error(vec![ValidationError::new(
-                            &error_message,
-                            None,
+                    DecoratorValue::String(_) | DecoratorValue::Bool(_) | DecoratorValue::List(_) => {
+                        return DecoratorValidationResult::Error(vec![invalid_argument(
+                            "max",
+                            &decorator_value,
+                            "a number",
                         )]);
                     }
-                    DecoratorValue::Integer(dec_value) => match value {
-                        ValueType::Number(v) => match v.cmp(&dec_value) {
-                            CompareResult::Greater => {
-                                let error_message =
-                                    format!("{v} is too large. Maximum value is {dec_value}.");
-
-                                return DecoratorValidationResult::Error(vec![
-                                    ValidationError::new(
-                                        &error_message,
-                                        Some(ValueType::Number(v)),
-                                    ),
-                                ]);
-                            }
-                            _ => return DecoratorValidationResult::Ok,
-                        },
-                        ValueType::String(v) => {
-                            if dec_value < v.len() as f64 {
-                                let error_message =
-                                    format!("'{v}' is too long. Maximum length is {dec_value}.");
-
-                                return DecoratorValidationResult::Error(vec![
-                                    ValidationError::new(
-                                        &error_message,
-                                        Some(ValueType::String(v)),
-                                    ),
-                                ]);
-                            }
+                    DecoratorValue::Integer(_) | DecoratorValue::Float(_) => {
+                        let dec_value = decorator_value.as_f64().unwrap_or_default();
 
-                            return DecoratorValidationResult::Ok;
-                        }
-                        ValueType::StringArray(v) => {
-                            let mut errors: Vec<ValidationError> = Vec::new();
-                            for s in v {
-                                if dec_value < s.len() as f64 {
-                                    let error_message = format!(
-                                        "'{s}' is too long. Maximum length is {dec_value}."
-                                    );
-
-                                    errors.push(ValidationError::new(
-                                        &error_message,
-                                        Some(ValueType::String(s)),
-                                    ));
+                        match value {
+                            ValueType::Integer(v) => match Compare::cmp(&v, &dec_value) {
+                                CompareResult::Greater => {
+                                    let error_message =
+                                        format!("{v} is too large. Maximum value is {dec_value}.");
+
+                                    return DecoratorValidationResult::Error(vec![
+                                        ValidationError::new(
+                                            &error_message,
+                                            Some(ValueType::Integer(v)),
+                                        ),
+                                    ]);
+                                }
+                                _ => return DecoratorValidationResult::Ok,
+                            },
+                            ValueType::Number(v) => match v.cmp(&dec_value) {
+                                CompareResult::Greater => {
+                                    let error_message =
+                                        format!("{v} is too large. Maximum value is {dec_value}.");
+
+                                    return DecoratorValidationResult::Error(vec![
+                                        ValidationError::new(
+                                            &error_message,
+                                            Some(ValueType::Number(v)),
+                                        ),
+                                    ]);
+                                }
+                                _ => return DecoratorValidationResult::Ok,
+                            },
+                            ValueType::String(v) => {
+                                if dec_value < v.len() as f64 {
+                                    let error_message =
+                                        format!("'{v}' is too long. Maximum length is {dec_value}.");
+
+                                    return DecoratorValidationResult::Error(vec![
+                                        ValidationError::new(
+                                            &error_message,
+                                            Some(ValueType::String(v)),
+                                        ),
+                                    ]);
                                 }
-                            }
 
-                            if errors.len() != 0 {
-                                return DecoratorValidationResult::Error(errors);
-                            } else {
                                 return DecoratorValidationResult::Ok;
                             }
-                        }
-                        ValueType::NumberArray(v) => {
-                            let mut errors: Vec<ValidationError> = Vec::new();
-                            for num in v {
-                                match num.cmp(&dec_value) {
-                                    CompareResult::Greater => {
+                            ValueType::StringArray(v) => {
+                                let mut errors: Vec<ValidationError> = Vec::new();
+                                for s in v {
+                                    if dec_value < s.len() as f64 {
                                         let error_message = format!(
-                                            "{num} is too large. Maximum value is {dec_value}."
+                                            "'{s}' is too long. Maximum length is {dec_value}."
                                         );
 
-                                        println!("There was an error");
+                                        errors.push(ValidationError::new(
+                                            &error_message,
+                                            Some(ValueType::String(s)),
+                                        ));
+                                    }
+                                }
+
+                                if !errors.is_empty() {
+                                    return DecoratorValidationResult::Error(errors);
+                                } else {
+                                    return DecoratorValidationResult::Ok;
+                                }
+                            }
+                            ValueType::IntegerArray(v) => {
+                                let mut errors: Vec<ValidationError> = Vec::new();
+                                for num in v {
+                                    if Compare::cmp(&num, &dec_value) == CompareResult::Greater {
+                                        let error_message = format!(
+                                            "{num} is too large. Maximum value is {dec_value}."
+                                        );
 
                                         errors.push(ValidationError::new(
                                             &error_message,
-                                            Some(ValueType::Number(num)),
+                                            Some(ValueType::Integer(num)),
                                         ));
                                     }
-                                    _ => continue,
+                                }
+
+                                if !errors.is_empty() {
+                                    return DecoratorValidationResult::Error(errors);
+                                } else {
+                                    return DecoratorValidationResult::Ok;
                                 }
                             }
+                            ValueType::NumberArray(v) => {
+                                let mut errors: Vec<ValidationError> = Vec::new();
+                                for num in v {
+                                    match num.cmp(&dec_value) {
+                                        CompareResult::Greater => {
+                                            let error_message = format!(
+                                                "{num} is too large. Maximum value is {dec_value}."
+                                            );
+
+                                            errors.push(ValidationError::new(
+                                                &error_message,
+                                                Some(ValueType::Number(num)),
+                                            ));
+                                        }
+                                        _ => continue,
+                                    }
+                                }
 
-                            if errors.len() != 0 {
-                                return DecoratorValidationResult::Error(errors);
-                            } else {
-                                return DecoratorValidationResult::Ok;
+                                if !errors.is_empty() {
+                                    return DecoratorValidationResult::Error(errors);
+                                } else {
+                                    return DecoratorValidationResult::Ok;
+                                }
                             }
-                        }
+                    }
                     },
                     DecoratorValue::None => {
-                        let error_message = format!("The max decorator requires a value to be provided with it. Ex: `@max(5)`");
+                        let error_message = "The max decorator requires a value to be provided with it. Ex: `@max(5)`".to_string();
 
                         return DecoratorValidationResult::Error(vec![ValidationError::new(
                             &error_message,
@@ -296,18 +642,20 @@ pub fn get() -> HashMap<String, Decorator> {
                     }
                 },
             ),
-        ),
+        )
+        .with_whole(item_bound("max", "@max(5)", false)),
         // ====== startsWith ======
         Decorator::new(
             "startsWith",
             Box::new(|value, decorator_value| match decorator_value {
-                DecoratorValue::Integer(dec_value) => {
-                    let error_message =
-                    format!("'{dec_value}' is not valid for decorator type 'startsWith'. 'startsWith' requires a string value.");
-
-                    return DecoratorValidationResult::Error(vec![ValidationError::new(
-                        &error_message,
-                        None,
+                DecoratorValue::Integer(_)
+                | DecoratorValue::Float(_)
+                | DecoratorValue::Bool(_)
+                | DecoratorValue::List(_) => {
+                    return DecoratorValidationResult::Error(vec![invalid_argument(
+                        "startsWith",
+                        &decorator_value,
+                        "a string",
                     )]);
                 }
                 DecoratorValue::String(dec_value) => match value {
@@ -337,7 +685,7 @@ pub fn get() -> HashMap<String, Decorator> {
                             }
                         }
 
-                        if errors.len() != 0 {
+                        if !errors.is_empty() {
                             return DecoratorValidationResult::Error(errors);
                         } else {
                             return DecoratorValidationResult::Ok;
@@ -345,7 +693,7 @@ pub fn get() -> HashMap<String, Decorator> {
                     }
                     _ => {
                         let error_message =
-                        format!("startsWith does not support this variable type. startsWith only supports the string and string array types.");
+                        "startsWith does not support this variable type. startsWith only supports the string and string array types.".to_string();
 
                         return DecoratorValidationResult::Error(vec![ValidationError::new(
                             &error_message,
@@ -368,13 +716,14 @@ pub fn get() -> HashMap<String, Decorator> {
         Decorator::new(
             "endsWith",
             Box::new(|value, decorator_value| match decorator_value {
-                DecoratorValue::Integer(dec_value) => {
-                    let error_message =
-                    format!("'{dec_value}' is not valid for decorator type 'endsWith'. 'endsWith' requires a string value.");
-
-                    return DecoratorValidationResult::Error(vec![ValidationError::new(
-                        &error_message,
-                        None,
+                DecoratorValue::Integer(_)
+                | DecoratorValue::Float(_)
+                | DecoratorValue::Bool(_)
+                | DecoratorValue::List(_) => {
+                    return DecoratorValidationResult::Error(vec![invalid_argument(
+                        "endsWith",
+                        &decorator_value,
+                        "a string",
                     )]);
                 }
                 DecoratorValue::String(dec_value) => match value {
@@ -404,7 +753,7 @@ pub fn get() -> HashMap<String, Decorator> {
                             }
                         }
 
-                        if errors.len() != 0 {
+                        if !errors.is_empty() {
                             return DecoratorValidationResult::Error(errors);
                         } else {
                             return DecoratorValidationResult::Ok;
@@ -412,7 +761,7 @@ pub fn get() -> HashMap<String, Decorator> {
                     }
                     _ => {
                         let error_message =
-                        format!("endsWith does not support this variable type. endsWith only supports the string and string array types.");
+                        "endsWith does not support this variable type. endsWith only supports the string and string array types.".to_string();
 
                         return DecoratorValidationResult::Error(vec![ValidationError::new(
                             &error_message,
@@ -432,241 +781,1024 @@ pub fn get() -> HashMap<String, Decorator> {
             }),
         ),
         // ====== matches ======
-        Decorator::new(
-            "matches",
-            Box::new(|value, decorator_value| match decorator_value {
-                DecoratorValue::String(dec_value) => match value {
-                    ValueType::String(v) => {
-                        let pattern = format!(r"{dec_value}");
-                        let expression = Regex::new(&pattern);
-
-                        match expression {
-                            Ok(rgx) => {
-                                if rgx.is_match(&v) {
-                                    return DecoratorValidationResult::Ok;
-                                } else {
-                                    let error_message =
-                                        format!("'{v}' does not match '{pattern}'.");
+        // Requires the value to match the regex pattern
+        pattern("matches", true, cache.clone()),
+        // ====== doesNotMatch ======
+        // Requires the value to not match the regex pattern
+        pattern("doesNotMatch", false, cache),
+        // ====== duration / bytes / percent ======
+        // Requires the value to be written with a unit and to be within the optional bounds
+        unit("duration", Unit::Duration),
+        unit("bytes", Unit::Bytes),
+        unit("percent", Unit::Percent),
+        // ====== oneOf ======
+        // Requires the value to be one of the provided values
+        // If the value is a string or number array each value in the array is compared
+        Decorator::with_arguments(
+            "oneOf",
+            Box::new(|value, arguments| one_of("oneOf", value, arguments, true)),
+        ),
+        // ====== notOneOf ======
+        // Requires the value to not be any of the provided values
+        // If the value is a string or number array each value in the array is compared
+        Decorator::with_arguments(
+            "notOneOf",
+            Box::new(|value, arguments| one_of("notOneOf", value, arguments, false)),
+        ),
+        // ====== notIn ======
+        // Requires the value to not be any of the provided values ignoring case
+        // Useful for rejecting well known weak secrets
+        Decorator::with_arguments(
+            "notIn",
+            Box::new(|value, arguments| {
+                if let Some((i, arg)) = arguments.list.iter().enumerate().find(|(_, a)| a.name.is_some()) {
+                    let error_message = format!(
+                        "The notIn decorator does not accept the named argument '{}'.",
+                        arg.name.as_deref().unwrap_or_default()
+                    );
+
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(&error_message, None).at_argument(i),
+                    ]);
+                }
 
-                                    return DecoratorValidationResult::Error(vec![
-                                        ValidationError::new(
-                                            &error_message,
-                                            Some(ValueType::String(v)),
-                                        ),
-                                    ]);
-                                }
-                            }
-                            Err(_) => {
-                                let error_message = format!("Couldn't parse regex {pattern}.");
+                // Lists are flattened so that `@notIn(["a", "b"])` is the same as `@notIn("a", "b")`
+                let mut disallowed: Vec<String> = Vec::new();
 
-                                return DecoratorValidationResult::Error(vec![
-                                    ValidationError::new(&error_message, None),
-                                ]);
-                            }
+                for (_, v) in arguments.positional() {
+                    match v {
+                        DecoratorValue::List(items) => {
+                            disallowed.extend(items.iter().map(|item| item.raw().to_lowercase()))
                         }
+                        _ => disallowed.push(v.raw().to_lowercase()),
                     }
-                    ValueType::StringArray(values) => {
-                        let pattern = format!(r"{dec_value}");
-                        let expression = Regex::new(&pattern);
+                }
 
-                        match expression {
-                            Ok(rgx) => {
-                                let mut errors: Vec<ValidationError> = Vec::new();
-                                for v in values {
-                                    if !rgx.is_match(&v) {
-                                        let error_message =
-                                            format!("'{v}' does not match '{pattern}'.");
+                if disallowed.is_empty() {
+                    let error_message = "The notIn decorator requires at least one value to be provided with it. Ex: `@notIn(\"password\", \"admin\")`";
 
-                                        errors.push(ValidationError::new(
-                                            &error_message,
-                                            Some(ValueType::String(v)),
-                                        ));
-                                    }
-                                }
+                    return DecoratorValidationResult::Error(vec![ValidationError::new(
+                        error_message,
+                        None,
+                    )]);
+                }
 
-                                if errors.len() > 0 {
-                                    return DecoratorValidationResult::Error(errors);
-                                } else {
-                                    return DecoratorValidationResult::Ok;
-                                }
-                            }
-                            Err(_) => {
-                                let error_message = format!("Couldn't parse regex {pattern}.");
-
-                                return DecoratorValidationResult::Error(vec![
-                                    ValidationError::new(&error_message, None),
-                                ]);
-                            }
+                let values: Vec<(String, ValueType)> = match value {
+                    ValueType::String(v) => vec![(v.to_owned(), ValueType::String(v))],
+                    ValueType::StringArray(v) => v
+                        .into_iter()
+                        .map(|s| (s.to_owned(), ValueType::String(s)))
+                        .collect(),
+                    ValueType::Integer(v) => vec![(v.to_string(), ValueType::Integer(v))],
+                    ValueType::Number(v) => vec![(v.to_string(), ValueType::Number(v))],
+                    ValueType::IntegerArray(v) => v
+                        .into_iter()
+                        .map(|n| (n.to_string(), ValueType::Integer(n)))
+                        .collect(),
+                    ValueType::NumberArray(v) => v
+                        .into_iter()
+                        .map(|n| (n.to_string(), ValueType::Number(n)))
+                        .collect(),
+                };
+
+                let errors: Vec<ValidationError> = values
+                    .into_iter()
+                    .filter(|(v, _)| disallowed.contains(&v.to_lowercase()))
+                    .map(|(v, item)| {
+                        ValidationError::new(&format!("'{v}' is not allowed."), Some(item))
+                    })
+                    .collect();
+
+                if !errors.is_empty() {
+                    return DecoratorValidationResult::Error(errors);
+                } else {
+                    return DecoratorValidationResult::Ok;
+                }
+            }),
+        ),
+        // ====== notDefault ======
+        // Rejects placeholder values like `changeme` or `xxx` that were never replaced
+        Decorator::new(
+            "notDefault",
+            Box::new(|value, decorator_value| match decorator_value {
+                DecoratorValue::None => {
+                    let values = match value {
+                        ValueType::String(v) => vec![v],
+                        ValueType::StringArray(v) => v,
+                        _ => {
+                            let error_message = "notDefault does not support this variable type. notDefault only supports the string and string array types.";
+
+                            return DecoratorValidationResult::Error(vec![ValidationError::new(
+                                error_message,
+                                None,
+                            )]);
                         }
+                    };
+
+                    let errors: Vec<ValidationError> = values
+                        .into_iter()
+                        .filter(|v| secrets::is_placeholder(v))
+                        .map(|v| {
+                            let error_message = format!(
+                                "'{v}' looks like a placeholder. Replace it with a real value."
+                            );
+
+                            ValidationError::new(&error_message, Some(ValueType::String(v)))
+                        })
+                        .collect();
+
+                    if !errors.is_empty() {
+                        return DecoratorValidationResult::Error(errors);
+                    } else {
+                        return DecoratorValidationResult::Ok;
+                    }
+                }
+                _ => {
+                    let error_message = "The notDefault decorator does not accept a value. Ex: `@notDefault`";
+
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(error_message, None).at_argument(0),
+                    ]);
+                }
+            }),
+        ),
+        // ====== entropy ======
+        // Requires secrets to have at least the provided entropy in bits
+        Decorator::with_arguments(
+            "entropy",
+            Box::new(|value, arguments| {
+                if let Some((i, arg)) = arguments
+                    .list
+                    .iter()
+                    .enumerate()
+                    .find(|(_, a)| a.name.as_deref() != Some("min"))
+                {
+                    let error_message = match &arg.name {
+                        Some(arg_name) => format!(
+                            "The entropy decorator does not accept the named argument '{arg_name}'."
+                        ),
+                        None => "The entropy decorator only accepts the named argument 'min'. Ex: `@entropy(min=64)`".to_owned(),
+                    };
+
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(&error_message, None).at_argument(i),
+                    ]);
+                }
+
+                let min = match arguments.named("min") {
+                    Some((_, v @ (DecoratorValue::Integer(_) | DecoratorValue::Float(_)))) => {
+                        v.as_f64().unwrap_or_default()
                     }
+                    Some((i, v)) => {
+                        let error_message = format!(
+                            "'{}' is not valid for 'min'. 'min' requires a number value.",
+                            v.raw()
+                        );
+
+                        return DecoratorValidationResult::Error(vec![
+                            ValidationError::new(&error_message, None).at_argument(i),
+                        ]);
+                    }
+                    None => {
+                        let error_message = "The entropy decorator requires a minimum to be provided with it. Ex: `@entropy(min=64)`";
+
+                        return DecoratorValidationResult::Error(vec![ValidationError::new(
+                            error_message,
+                            None,
+                        )]);
+                    }
+                };
+
+                let values = match value {
+                    ValueType::String(v) => vec![v],
+                    ValueType::StringArray(v) => v,
                     _ => {
-                        let error_message =
-                        format!("matches does not support this variable type. matches only supports the string and string array types.");
+                        let error_message = "entropy does not support this variable type. entropy only supports the string and string array types.";
 
                         return DecoratorValidationResult::Error(vec![ValidationError::new(
-                            &error_message,
+                            error_message,
                             None,
                         )]);
                     }
-                },
-                DecoratorValue::Integer(_) => {
-                    let error_message = format!("You must provide a string value to matches.");
+                };
 
-                    return DecoratorValidationResult::Error(vec![ValidationError::new(
-                        &error_message,
-                        None,
-                    )]);
+                let mut errors: Vec<ValidationError> = Vec::new();
+
+                for v in values {
+                    let bits = secrets::entropy(&v);
+
+                    if let CompareResult::Less = Compare::cmp(&bits, &min) {
+                        // The value is left out of the message since it is likely a secret
+                        let error_message = format!(
+                            "Value is too weak. It has {bits:.1} bits of entropy but at least {min} are required."
+                        );
+
+                        errors.push(ValidationError::new(
+                            &error_message,
+                            Some(ValueType::String(v)),
+                        ));
+                    }
                 }
-                DecoratorValue::None => {
-                    let error_message =
-                    format!("The matches decorator requires a value to be provided with it. Ex: `@matches({})`", "\"index\"");
 
-                    return DecoratorValidationResult::Error(vec![ValidationError::new(
-                        &error_message,
-                        None,
-                    )]);
+                if !errors.is_empty() {
+                    return DecoratorValidationResult::Error(errors);
+                } else {
+                    return DecoratorValidationResult::Ok;
                 }
             }),
         ),
-        // ====== doesNotMatch ======
+        // ====== minItems ======
+        // Compares the decorator value to the number of items in the array
+        Decorator::with_arguments("minItems", item_bound("minItems", "@minItems(1)", true))
+            .validates_whole(),
+        // ====== maxItems ======
+        // Compares the decorator value to the number of items in the array
+        Decorator::with_arguments("maxItems", item_bound("maxItems", "@maxItems(10)", false))
+            .validates_whole(),
+        // ====== nonEmpty ======
+        // Requires arrays to have at least one item and strings to have at least one character
         Decorator::new(
-            "doesNotMatch",
+            "nonEmpty",
             Box::new(|value, decorator_value| match decorator_value {
-                DecoratorValue::String(dec_value) => match value {
-                    ValueType::String(v) => {
-                        let pattern = format!(r"{dec_value}");
-                        let expression = Regex::new(&pattern);
-
-                        match expression {
-                            Ok(rgx) => {
-                                if !rgx.is_match(&v) {
-                                    return DecoratorValidationResult::Ok;
-                                } else {
-                                    let error_message = format!("'{v}' matches '{pattern}'.");
+                DecoratorValue::None => {
+                    let empty = match &value {
+                        ValueType::String(v) => v.is_empty(),
+                        ValueType::StringArray(v) => v.is_empty(),
+                        ValueType::IntegerArray(v) => v.is_empty(),
+                        ValueType::NumberArray(v) => v.is_empty(),
+                        ValueType::Integer(_) | ValueType::Number(_) => false,
+                    };
+
+                    if empty {
+                        return DecoratorValidationResult::Error(vec![ValidationError::new(
+                            "Value must not be empty.",
+                            None,
+                        )]);
+                    }
 
-                                    return DecoratorValidationResult::Error(vec![
-                                        ValidationError::new(
-                                            &error_message,
-                                            Some(ValueType::String(v)),
-                                        ),
-                                    ]);
-                                }
-                            }
-                            Err(_) => {
-                                let error_message = format!("Couldn't parse regex {pattern}.");
+                    return DecoratorValidationResult::Ok;
+                }
+                _ => {
+                    let error_message = "The nonEmpty decorator does not accept a value. Ex: `@nonEmpty`";
 
-                                return DecoratorValidationResult::Error(vec![
-                                    ValidationError::new(&error_message, None),
-                                ]);
-                            }
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(error_message, None).at_argument(0),
+                    ]);
+                }
+            }),
+        )
+        .validates_whole(),
+        // ====== integer ======
+        // Requires numbers to be whole numbers
+        Decorator::new(
+            "integer",
+            Box::new(|value, decorator_value| match decorator_value {
+                DecoratorValue::None => match value {
+                    ValueType::Integer(_) | ValueType::IntegerArray(_) => return DecoratorValidationResult::Ok,
+                    ValueType::Number(v) => {
+                        if v.fract() != 0.0 {
+                            let error_message = format!("'{v}' is not an integer.");
+
+                            return DecoratorValidationResult::Error(vec![ValidationError::new(
+                                &error_message,
+                                Some(ValueType::Number(v)),
+                            )]);
                         }
-                    }
-                    ValueType::StringArray(values) => {
-                        let pattern = format!(r"{dec_value}");
-                        let expression = Regex::new(&pattern);
 
-                        match expression {
-                            Ok(rgx) => {
-                                let mut errors: Vec<ValidationError> = Vec::new();
-                                for v in values {
-                                    if rgx.is_match(&v) {
-                                        let error_message = format!("'{v}' matches '{pattern}'.");
+                        return DecoratorValidationResult::Ok;
+                    }
+                    ValueType::NumberArray(v) => {
+                        let errors: Vec<ValidationError> = v
+                            .into_iter()
+                            .filter(|n| n.fract() != 0.0)
+                            .map(|n| {
+                                ValidationError::new(
+                                    &format!("'{n}' is not an integer."),
+                                    Some(ValueType::Number(n)),
+                                )
+                            })
+                            .collect();
+
+                        if !errors.is_empty() {
+                            return DecoratorValidationResult::Error(errors);
+                        }
 
-                                        errors.push(ValidationError::new(
-                                            &error_message,
-                                            Some(ValueType::String(v)),
-                                        ));
-                                    }
-                                }
+                        return DecoratorValidationResult::Ok;
+                    }
+                    _ => {
+                        let error_message = "integer does not support this variable type. integer only supports the number and number array types.";
 
-                                if errors.len() > 0 {
-                                    return DecoratorValidationResult::Error(errors);
-                                } else {
-                                    return DecoratorValidationResult::Ok;
-                                }
-                            }
-                            Err(_) => {
-                                let error_message = format!("Couldn't parse regex {pattern}.");
+                        return DecoratorValidationResult::Error(vec![ValidationError::new(
+                            error_message,
+                            None,
+                        )]);
+                    }
+                },
+                _ => {
+                    let error_message = "The integer decorator does not accept a value. Ex: `@integer`";
 
-                                return DecoratorValidationResult::Error(vec![
-                                    ValidationError::new(&error_message, None),
-                                ]);
-                            }
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(error_message, None).at_argument(0),
+                    ]);
+                }
+            }),
+        ),
+        // ====== uniqueItems ======
+        // Requires every item in the array to be unique
+        Decorator::new(
+            "uniqueItems",
+            Box::new(|value, decorator_value| match decorator_value {
+                DecoratorValue::None => {
+                    let items: Vec<(String, ValueType)> = match value {
+                        ValueType::StringArray(v) => v
+                            .into_iter()
+                            .map(|s| (s.to_owned(), ValueType::String(s)))
+                            .collect(),
+                        ValueType::IntegerArray(v) => v
+                            .into_iter()
+                            .map(|n| (n.to_string(), ValueType::Integer(n)))
+                            .collect(),
+                        ValueType::NumberArray(v) => v
+                            .into_iter()
+                            .map(|n| (n.to_string(), ValueType::Number(n)))
+                            .collect(),
+                        _ => return array_type_error("uniqueItems"),
+                    };
+
+                    let mut errors: Vec<ValidationError> = Vec::new();
+                    let mut reported: Vec<String> = Vec::new();
+
+                    for (i, (item, item_value)) in items.iter().enumerate() {
+                        let duplicated = items[..i].iter().any(|(other, _)| other == item);
+
+                        if duplicated && !reported.contains(item) {
+                            let error_message = format!("'{item}' appears more than once.");
+
+                            errors.push(ValidationError::new(
+                                &error_message,
+                                Some(item_value.to_owned()),
+                            ));
+                            reported.push(item.to_owned());
                         }
                     }
+
+                    if !errors.is_empty() {
+                        return DecoratorValidationResult::Error(errors);
+                    } else {
+                        return DecoratorValidationResult::Ok;
+                    }
+                }
+                _ => {
+                    let error_message = "The uniqueItems decorator does not accept a value. Ex: `@uniqueItems`";
+
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(error_message, None).at_argument(0),
+                    ]);
+                }
+            }),
+        )
+        .validates_whole(),
+        // ====== sorted ======
+        // Requires the items in the array to be sorted in ascending (default) or descending order
+        Decorator::new(
+            "sorted",
+            Box::new(|value, decorator_value| {
+                let descending = match &decorator_value {
+                    DecoratorValue::None => false,
+                    DecoratorValue::String(v) if v == "asc" => false,
+                    DecoratorValue::String(v) if v == "desc" => true,
                     _ => {
-                        let error_message =
-                        format!("doesNotMatch does not support this variable type. doesNotMatch only supports the string and string array types.");
+                        let error_message = format!(
+                            "'{}' is not valid for decorator type 'sorted'. 'sorted' accepts \"asc\" or \"desc\".",
+                            decorator_value.raw()
+                        );
+
+                        return DecoratorValidationResult::Error(vec![
+                            ValidationError::new(&error_message, None).at_argument(0),
+                        ]);
+                    }
+                };
+
+                let order = if descending { "descending" } else { "ascending" };
+
+                let out_of_order = match value {
+                    ValueType::StringArray(v) => v
+                        .windows(2)
+                        .find(|w| if descending { w[0] < w[1] } else { w[0] > w[1] })
+                        .map(|w| (w[1].to_owned(), ValueType::String(w[1].to_owned()))),
+                    ValueType::IntegerArray(v) => v
+                        .windows(2)
+                        .find(|w| if descending { w[0] < w[1] } else { w[0] > w[1] })
+                        .map(|w| (w[1].to_string(), ValueType::Integer(w[1]))),
+                    ValueType::NumberArray(v) => v
+                        .windows(2)
+                        .find(|w| if descending { w[0] < w[1] } else { w[0] > w[1] })
+                        .map(|w| (w[1].to_string(), ValueType::Number(w[1]))),
+                    _ => return array_type_error("sorted"),
+                };
+
+                match out_of_order {
+                    Some((item, item_value)) => {
+                        let error_message = format!(
+                            "'{item}' is out of order. Items must be sorted in {order} order."
+                        );
 
                         return DecoratorValidationResult::Error(vec![ValidationError::new(
                             &error_message,
-                            None,
+                            Some(item_value),
                         )]);
                     }
-                },
-                DecoratorValue::Integer(_) => {
-                    let error_message = format!("You must provide a string value to doesNotMatch.");
+                    None => return DecoratorValidationResult::Ok,
+                }
+            }),
+        )
+        .validates_whole(),
+    ];
+
+    for dec in decorators {
+        map.insert(dec.name.to_owned(), dec);
+    }
+
+    map
+}
+
+/// Compiled regex patterns keyed by their flags and pattern
+///
+/// The cache lives as long as the decorators so each pattern is compiled once per parse.
+type RegexCache = Rc<RefCell<HashMap<String, Result<Regex, String>>>>;
+
+/// Returns the reason the regex couldn't be compiled on a single line
+///
+/// Syntax errors are printed by the regex crate with the pattern and a pointer above the reason so only the
+/// reason is kept since the diagnostic already points at the pattern.
+fn regex_error(err: &regex::Error) -> String {
+    let message = err.to_string();
+
+    let reason = message.lines().last().unwrap_or_default().trim();
+
+    reason.strip_prefix("error: ").unwrap_or(reason).trim_end_matches('.').to_owned()
+}
+
+/// Compiles the pattern with the provided flags or returns it from the cache
+///
+/// Supported flags are `i` (case-insensitive), `m` (multiline) and `s` (dot matches new lines).
+fn compile(cache: &RegexCache, pattern: &str, flags: &str) -> Result<Regex, String> {
+    let cache_key = format!("{flags}/{pattern}");
+
+    if let Some(compiled) = cache.borrow().get(&cache_key) {
+        return compiled.to_owned();
+    }
+
+    let compiled = RegexBuilder::new(pattern)
+        .case_insensitive(flags.contains('i'))
+        .multi_line(flags.contains('m'))
+        .dot_matches_new_line(flags.contains('s'))
+        .build()
+        .map_err(|err| format!("Couldn't parse regex {pattern}: {}.", regex_error(&err)));
+
+    cache.borrow_mut().insert(cache_key, compiled.to_owned());
+
+    compiled
+}
+
+/// Creates a decorator that validates strings against a regex pattern
+///
+/// Flags can be provided with the `flags` argument. Ex: `@matches("^[a-z]+$", flags="i")`
+fn pattern(name: &'static str, should_match: bool, cache: RegexCache) -> Decorator {
+    Decorator::with_arguments(
+        name,
+        Box::new(move |value, arguments| {
+            for (i, arg) in arguments.list.iter().enumerate() {
+                let error_message = match &arg.name {
+                    Some(arg_name) if arg_name != "flags" => format!(
+                        "The {name} decorator does not accept the named argument '{arg_name}'."
+                    ),
+                    None if i > 0 => format!("The {name} decorator only accepts a single pattern."),
+                    _ => continue,
+                };
+
+                return DecoratorValidationResult::Error(vec![
+                    ValidationError::new(&error_message, None).at_argument(i),
+                ]);
+            }
+
+            let (pattern_index, pattern) = match arguments.get(0) {
+                Some((i, DecoratorValue::String(pattern))) => (i, pattern.to_owned()),
+                Some((_, v)) => {
+                    return DecoratorValidationResult::Error(vec![invalid_argument(
+                        name, v, "a string",
+                    )]);
+                }
+                None => {
+                    let error_message = format!(
+                        "The {name} decorator requires a value to be provided with it. Ex: `@{name}({})`",
+                        "\"index\""
+                    );
 
                     return DecoratorValidationResult::Error(vec![ValidationError::new(
                         &error_message,
                         None,
                     )]);
                 }
-                DecoratorValue::None => {
-                    let error_message =
-                    format!("The doesNotMatch decorator requires a value to be provided with it. Ex: `@doesNotMatch({})`", "\"index\"");
+            };
+
+            let flags = match arguments.named("flags") {
+                Some((_, DecoratorValue::String(flags)))
+                    if flags.chars().all(|c| matches!(c, 'i' | 'm' | 's')) =>
+                {
+                    flags.to_owned()
+                }
+                Some((i, v)) => {
+                    let error_message = format!(
+                        "'{}' is not valid for 'flags'. 'flags' can only contain 'i' (case-insensitive), 'm' (multiline) and 's' (dot matches new lines).",
+                        v.raw()
+                    );
+
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(&error_message, None).at_argument(i),
+                    ]);
+                }
+                None => String::new(),
+            };
+
+            let rgx = match compile(&cache, &pattern, &flags) {
+                Ok(rgx) => rgx,
+                Err(error_message) => {
+                    return DecoratorValidationResult::Error(vec![
+                        ValidationError::new(&error_message, None).at_argument(pattern_index),
+                    ]);
+                }
+            };
+
+            let values = match value {
+                ValueType::String(v) => vec![v],
+                ValueType::StringArray(values) => values,
+                _ => {
+                    let error_message = format!(
+                        "{name} does not support this variable type. {name} only supports the string and string array types."
+                    );
 
                     return DecoratorValidationResult::Error(vec![ValidationError::new(
                         &error_message,
                         None,
                     )]);
                 }
-            }),
-        ),
-    ];
+            };
 
-    for dec in decorators {
-        map.insert(dec.name.to_owned(), dec);
+            let mut errors: Vec<ValidationError> = Vec::new();
+
+            for v in values {
+                if rgx.is_match(&v) != should_match {
+                    let error_message = if should_match {
+                        format!("'{v}' does not match '{pattern}'.")
+                    } else {
+                        format!("'{v}' matches '{pattern}'.")
+                    };
+
+                    errors.push(ValidationError::new(
+                        &error_message,
+                        Some(ValueType::String(v)),
+                    ));
+                }
+            }
+
+            if !errors.is_empty() {
+                return DecoratorValidationResult::Error(errors);
+            } else {
+                return DecoratorValidationResult::Ok;
+            }
+        }),
+    )
+}
+
+/// Creates a decorator that documents the key with a string and doesn't validate the value
+fn documentation(name: &'static str, example: &'static str) -> Decorator {
+    Decorator::new(
+        name,
+        Box::new(move |_value, decorator_value| match decorator_value {
+            DecoratorValue::String(_) => DecoratorValidationResult::Ok,
+            DecoratorValue::None => {
+                let error_message = format!(
+                    "The {name} decorator requires a value to be provided with it. Ex: `@{name}(\"{example}\")`"
+                );
+
+                return DecoratorValidationResult::Error(vec![ValidationError::new(
+                    &error_message,
+                    None,
+                )]);
+            }
+            _ => {
+                return DecoratorValidationResult::Error(vec![invalid_argument(
+                    name,
+                    &decorator_value,
+                    "a string",
+                )]);
+            }
+        }),
+    )
+    .validates_whole()
+}
+
+/// Creates a decorator that validates values written with a unit like `30s` or `10MB`
+///
+/// The optional `min` and `max` arguments are written in the same unit. Ex: `@duration(min="1s", max="5m")`
+fn unit(name: &'static str, unit: Unit) -> Decorator {
+    Decorator::with_arguments(
+        name,
+        Box::new(move |value, arguments| {
+            let mut bounds: Vec<(&str, f64, &DecoratorValue)> = Vec::new();
+
+            for (i, arg) in arguments.list.iter().enumerate() {
+                let bound = match arg.name.as_deref() {
+                    Some(bound @ ("min" | "max")) => bound,
+                    Some(arg_name) => {
+                        let error_message = format!(
+                            "The {name} decorator does not accept the named argument '{arg_name}'."
+                        );
+
+                        return DecoratorValidationResult::Error(vec![
+                            ValidationError::new(&error_message, None).at_argument(i),
+                        ]);
+                    }
+                    None => {
+                        let error_message = format!(
+                            "The {name} decorator only accepts the named arguments 'min' and 'max'. Ex: `@{name}(min=\"{}\")`",
+                            unit.example()
+                        );
+
+                        return DecoratorValidationResult::Error(vec![
+                            ValidationError::new(&error_message, None).at_argument(i),
+                        ]);
+                    }
+                };
+
+                let parsed = match &arg.value {
+                    DecoratorValue::String(v) => unit.parse(v),
+                    v => Err(format!(
+                        "'{}' is not a valid {unit}. Ex: `{}`",
+                        v.raw(),
+                        unit.example()
+                    )),
+                };
+
+                match parsed {
+                    Ok(parsed) => bounds.push((bound, parsed, &arg.value)),
+                    Err(error_message) => {
+                        return DecoratorValidationResult::Error(vec![
+                            ValidationError::new(&error_message, None).at_argument(i),
+                        ]);
+                    }
+                }
+            }
+
+            let values = match value {
+                ValueType::String(v) => vec![v],
+                ValueType::StringArray(values) => values,
+                ValueType::Integer(_)
+                | ValueType::Number(_)
+                | ValueType::IntegerArray(_)
+                | ValueType::NumberArray(_) => {
+                    let error_message = format!(
+                        "{value} is missing a unit. {name} values must be written with a unit. Ex: `{}`",
+                        unit.example()
+                    );
+
+                    return DecoratorValidationResult::Error(vec![ValidationError::new(
+                        &error_message,
+                        None,
+                    )]);
+                }
+            };
+
+            let mut errors: Vec<ValidationError> = Vec::new();
+
+            for v in values {
+                let parsed = match unit.parse(&v) {
+                    Ok(parsed) => parsed,
+                    Err(error_message) => {
+                        errors.push(ValidationError::new(
+                            &error_message,
+                            Some(ValueType::String(v)),
+                        ));
+                        continue;
+                    }
+                };
+
+                for (bound, limit, raw) in &bounds {
+                    let error_message = match (*bound, Compare::cmp(&parsed, limit)) {
+                        ("min", CompareResult::Less) => {
+                            format!("'{v}' is too small. Minimum value is {}.", raw.raw())
+                        }
+                        ("max", CompareResult::Greater) => {
+                            format!("'{v}' is too large. Maximum value is {}.", raw.raw())
+                        }
+                        _ => continue,
+                    };
+
+                    errors.push(ValidationError::new(
+                        &error_message,
+                        Some(ValueType::String(v.to_owned())),
+                    ));
+                }
+            }
+
+            if !errors.is_empty() {
+                return DecoratorValidationResult::Error(errors);
+            } else {
+                return DecoratorValidationResult::Ok;
+            }
+        }),
+    )
+}
+
+/// Compares a number value to a number decorator value
+///
+/// Returns `None` if either of the values is not a number.
+pub fn compare_number(value: &ValueType, decorator_value: &DecoratorValue) -> Option<CompareResult> {
+    match (value, decorator_value) {
+        (ValueType::Integer(v), DecoratorValue::Integer(d)) => Some(Compare::cmp(v, d)),
+        (ValueType::Integer(v), DecoratorValue::Float(d)) => Some(Compare::cmp(v, d)),
+        (ValueType::Number(v), DecoratorValue::Integer(d)) => Some(Compare::cmp(v, d)),
+        (ValueType::Number(v), DecoratorValue::Float(d)) => Some(Compare::cmp(v, d)),
+        _ => None,
     }
+}
 
-    map
+/// Wraps a validator that accepts a single positional value so any other argument is rejected
+fn single_value(
+    name: &str,
+    validator: Box<dyn Fn(ValueType, DecoratorValue) -> DecoratorValidationResult>,
+) -> Validator {
+    let decorator_name = name.to_owned();
+
+    Box::new(move |value, arguments| {
+        for (i, arg) in arguments.list.iter().enumerate() {
+            let error_message = match &arg.name {
+                Some(arg_name) => format!(
+                    "The {decorator_name} decorator does not accept the named argument '{arg_name}'."
+                ),
+                None if i > 0 => format!(
+                    "The {decorator_name} decorator only accepts a single value."
+                ),
+                None => continue,
+            };
+
+            return DecoratorValidationResult::Error(vec![ValidationError::new(
+                &error_message,
+                None,
+            )
+            .at_argument(i)]);
+        }
+
+        let decorator_value = arguments
+            .get(0)
+            .map(|(_, v)| v.to_owned())
+            .unwrap_or(DecoratorValue::None);
+
+        validator(value, decorator_value)
+    })
+}
+
+/// Compares the decorator value to the number of items in the array
+///
+/// Used by `minItems` and `maxItems` and by `min` and `max` when `each=false` is provided.
+fn item_bound(name: &'static str, example: &'static str, at_least: bool) -> Validator {
+    single_value(
+        name,
+        Box::new(move |value, decorator_value| match decorator_value {
+            DecoratorValue::Integer(dec_value) => match item_count(&value) {
+                Some(count) => {
+                    if at_least && (count as i64) < dec_value {
+                        let error_message =
+                            format!("Expected at least {dec_value} items but found {count}.");
+
+                        return DecoratorValidationResult::Error(vec![ValidationError::new(
+                            &error_message,
+                            Some(value),
+                        )]);
+                    }
+
+                    if !at_least && (count as i64) > dec_value {
+                        let error_message =
+                            format!("Expected at most {dec_value} items but found {count}.");
+
+                        return DecoratorValidationResult::Error(vec![ValidationError::new(
+                            &error_message,
+                            Some(value),
+                        )]);
+                    }
+
+                    return DecoratorValidationResult::Ok;
+                }
+                None => return array_type_error(name),
+            },
+            DecoratorValue::None => {
+                let error_message = format!(
+                    "The {name} decorator requires a value to be provided with it. Ex: `{example}`"
+                );
+
+                return DecoratorValidationResult::Error(vec![ValidationError::new(
+                    &error_message,
+                    None,
+                )]);
+            }
+            _ => {
+                return DecoratorValidationResult::Error(vec![invalid_argument(
+                    name,
+                    &decorator_value,
+                    "a whole number",
+                )]);
+            }
+        }),
+    )
+}
+
+/// Returns the number of items in the value if it is an array
+fn item_count(value: &ValueType) -> Option<usize> {
+    match value {
+        ValueType::StringArray(v) => Some(v.len()),
+        ValueType::IntegerArray(v) => Some(v.len()),
+        ValueType::NumberArray(v) => Some(v.len()),
+        _ => None,
+    }
+}
+
+/// Creates the error for decorators that only support arrays
+fn array_type_error(name: &str) -> DecoratorValidationResult {
+    let error_message = format!(
+        "{name} does not support this variable type. {name} only supports the string array and number array types."
+    );
+
+    return DecoratorValidationResult::Error(vec![ValidationError::new(&error_message, None)]);
 }
 
-/// Parses the decorator syntax and returns its key and value if it has one
+/// Shared validation for `oneOf` and `notOneOf`
+///
+/// When `allowed` is true the values are the only allowed values otherwise they are the
+/// values that are not allowed.
+fn one_of(
+    name: &str,
+    value: ValueType,
+    arguments: &DecoratorArguments,
+    allowed: bool,
+) -> DecoratorValidationResult {
+    if let Some((i, arg)) = arguments.list.iter().enumerate().find(|(_, a)| a.name.is_some()) {
+        let error_message = format!(
+            "The {name} decorator does not accept the named argument '{}'.",
+            arg.name.as_deref().unwrap_or_default()
+        );
+
+        return DecoratorValidationResult::Error(vec![
+            ValidationError::new(&error_message, None).at_argument(i)
+        ]);
+    }
+
+    // Lists are flattened so that `@oneOf(["a", "b"])` is the same as `@oneOf("a", "b")`
+    let mut decorator_values: Vec<DecoratorValue> = Vec::new();
+
+    for (_, v) in arguments.positional() {
+        match v {
+            DecoratorValue::List(items) => decorator_values.extend(items.to_owned()),
+            _ => decorator_values.push(v.to_owned()),
+        }
+    }
+
+    if decorator_values.is_empty() {
+        let error_message = format!(
+            "The {name} decorator requires at least one value to be provided with it. Ex: `@{name}(\"debug\", \"info\")`"
+        );
+
+        return DecoratorValidationResult::Error(vec![ValidationError::new(&error_message, None)]);
+    }
+
+    let listed: Vec<String> = decorator_values
+        .iter()
+        .map(|v| match v {
+            DecoratorValue::String(s) => format!("'{s}'"),
+            _ => v.to_string(),
+        })
+        .collect();
+    let listed = listed.join(", ");
+
+    let strings: Vec<String> = decorator_values
+        .iter()
+        .filter_map(|v| match v {
+            DecoratorValue::String(s) => Some(s.to_owned()),
+            _ => None,
+        })
+        .collect();
+
+    let string_error = |v: &str| -> Option<ValidationError> {
+        let found = strings.iter().any(|s| s == v);
+
+        if found == allowed {
+            return None;
+        }
+
+        let error_message = if allowed {
+            match closest_match(v, &strings) {
+                Some(suggestion) => {
+                    format!("'{v}' is not one of {listed}. Did you mean '{suggestion}'?")
+                }
+                None => format!("'{v}' is not one of {listed}."),
+            }
+        } else {
+            format!("'{v}' is not allowed. Disallowed values are {listed}.")
+        };
+
+        Some(ValidationError::new(
+            &error_message,
+            Some(ValueType::String(v.to_owned())),
+        ))
+    };
+
+    let number_error = |v: ValueType| -> Option<ValidationError> {
+        let found = decorator_values
+            .iter()
+            .any(|d| compare_number(&v, d) == Some(CompareResult::Equal));
+
+        if found == allowed {
+            return None;
+        }
+
+        let error_message = if allowed {
+            format!("{v} is not one of {listed}.")
+        } else {
+            format!("{v} is not allowed. Disallowed values are {listed}.")
+        };
+
+        Some(ValidationError::new(&error_message, Some(v)))
+    };
+
+    let errors: Vec<ValidationError> = match value {
+        ValueType::String(v) => string_error(&v).into_iter().collect(),
+        ValueType::Integer(_) | ValueType::Number(_) => number_error(value).into_iter().collect(),
+        ValueType::StringArray(values) => values.iter().filter_map(|v| string_error(v)).collect(),
+        ValueType::IntegerArray(values) => values
+            .into_iter()
+            .filter_map(|v| number_error(ValueType::Integer(v)))
+            .collect(),
+        ValueType::NumberArray(values) => values
+            .into_iter()
+            .filter_map(|v| number_error(ValueType::Number(v)))
+            .collect(),
+    };
+
+    if !errors.is_empty() {
+        return DecoratorValidationResult::Error(errors);
+    } else {
+        return DecoratorValidationResult::Ok;
+    }
+}
+
+/// Parses the decorator syntax and returns its key and arguments if it has any
 ///
 /// # Parameters
-/// - `dec`: Should come in the format of `'decorator(value)'` or `'decorator'`
+/// - `dec`: Should come in the format of `'decorator(value, name=value)'` or `'decorator'`
 ///
 /// # Returns
-/// The key (before the parentheses) and value (inside of the parentheses) of the decorator
+/// The key (before the parentheses) and arguments (inside of the parentheses) of the decorator
+/// or an error if the arguments are not valid syntax
 ///
 /// # Examples
 /// ```
-/// let decorator = "startsWith(\"https\");";
+/// let decorator = "startsWith(\"https\")";
 ///
-/// let decorator_info = vnv::decorators::parse(decorator);
+/// let decorator_info = vnv::decorators::parse(decorator).unwrap();
 ///
 /// assert_eq!(decorator_info.key, "startsWith");
 /// ```
-pub fn parse(dec: &str) -> DecoratorParseResult {
+pub fn parse(dec: &str) -> Result<DecoratorParseResult, SyntaxError> {
     let start_parens = dec.find('(');
     match start_parens {
         Some(index) => {
-            let end_parens = dec.rfind(')').unwrap_or(dec.len());
-
             let key = &dec[0..index]; // Gets the value before the parentheses
-            let value = &dec[index + 1..end_parens]; // Gets the value between the parentheses
+            let rest = dec[index + 1..].trim_end();
+
+            if !rest.ends_with(')') {
+                return Err(SyntaxError::new(
+                    "Expected ')' to close the decorator arguments.",
+                    dec.trim_end().chars().count(),
+                    1,
+                ));
+            }
+
+            let value = &rest[..rest.len() - 1]; // Gets the value between the parentheses
 
-            return DecoratorParseResult {
+            return Ok(DecoratorParseResult {
                 key: key.to_owned(),
-                value: DecoratorValue::from_str(value),
-            };
+                arguments: arguments::parse(value, key.chars().count() + 1)?,
+                position: FilePosition::new(),
+                span: Span::default(),
+            });
         }
         None => {
-            return DecoratorParseResult {
+            return Ok(DecoratorParseResult {
                 key: dec.to_owned(),
-                value: DecoratorValue::None,
-            }
+                arguments: DecoratorArguments::default(),
+                position: FilePosition::new(),
+                span: Span::default(),
+            })
         }
     };
 }
@@ -0,0 +1,275 @@
+use crate::parsing::{Environment, FilePosition, ParseResult};
+use serde::Serialize;
+use std::fmt;
+
+pub mod render;
+
+pub use render::{mask, Format, Renderer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
+        }
+    }
+}
+
+/// A range of the source in bytes, the end is exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+}
+
+/// A secondary span that gives context to a diagnostic
+#[derive(Debug, Clone, Serialize)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A suggested edit that resolves the diagnostic
+#[derive(Debug, Clone, Serialize)]
+pub struct Fix {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
+}
+
+/// A problem found in a .vnv file
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Identifies the kind of problem. Key errors use the name of the decorator that reported them.
+    pub code: String,
+    pub message: String,
+    /// The primary span of the diagnostic
+    pub span: Span,
+    /// The line and column of the start of the span
+    pub position: FilePosition,
+    /// The number of characters the diagnostic spans from its position
+    pub length: u32,
+    pub labels: Vec<Label>,
+    pub fix: Option<Fix>,
+    /// The key the diagnostic was found on if it came from validating a key
+    pub key: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, code: &str, message: &str) -> Self {
+        Diagnostic {
+            severity,
+            code: code.to_owned(),
+            message: message.to_owned(),
+            span: Span::default(),
+            position: FilePosition::new(),
+            length: 1,
+            labels: Vec::new(),
+            fix: None,
+            key: None,
+        }
+    }
+
+    pub fn error(code: &str, message: &str) -> Self {
+        Diagnostic::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: &str, message: &str) -> Self {
+        Diagnostic::new(Severity::Warning, code, message)
+    }
+
+    /// Places the diagnostic at the position spanning the provided number of characters
+    pub fn at(mut self, index: &LineIndex, position: FilePosition, length: u32) -> Self {
+        self.span = index.span(position, length.max(1));
+        self.position = position;
+        self.length = length.max(1);
+        self
+    }
+
+    /// Places the diagnostic at the span
+    pub fn spanning(mut self, index: &LineIndex, span: Span) -> Self {
+        self.position = index.position(span.start);
+        self.length = (index.content[span.start..span.end].chars().count() as u32).max(1);
+        self.span = span;
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, message: &str) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.to_owned(),
+        });
+        self
+    }
+
+    pub fn with_fix(mut self, message: &str, span: Span, replacement: &str) -> Self {
+        self.fix = Some(Fix {
+            message: message.to_owned(),
+            span,
+            replacement: replacement.to_owned(),
+        });
+        self
+    }
+
+    pub fn for_key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_owned());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}[{}]: {}",
+            self.position.line, self.position.column, self.severity, self.code, self.message
+        )
+    }
+}
+
+/// Converts between line and column positions and byte offsets of the source
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    content: &'a str,
+    /// The byte offset each line starts at
+    starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(content: &'a str) -> Self {
+        let mut starts = vec![0];
+
+        for (i, c) in content.char_indices() {
+            if c == '\n' {
+                starts.push(i + 1);
+            }
+        }
+
+        LineIndex { content, starts }
+    }
+
+    /// Returns the text of the line (1 based) without the line ending
+    pub fn line(&self, line: u32) -> &'a str {
+        let index = (line.max(1) - 1) as usize;
+
+        match self.starts.get(index) {
+            Some(start) => {
+                let end = self.starts.get(index + 1).copied().unwrap_or(self.content.len());
+
+                self.content[*start..end].trim_end_matches(['\n', '\r'])
+            }
+            None => "",
+        }
+    }
+
+    /// Returns the text of the source covered by the span
+    pub fn text(&self, span: Span) -> &'a str {
+        let end = span.end.min(self.content.len());
+
+        &self.content[span.start.min(end)..end]
+    }
+
+    /// Returns the byte offset of the position
+    pub fn offset(&self, position: FilePosition) -> usize {
+        let index = (position.line.max(1) - 1) as usize;
+
+        let start = match self.starts.get(index) {
+            Some(start) => *start,
+            None => return self.content.len(),
+        };
+
+        let line = self.line(position.line);
+
+        let column = line
+            .char_indices()
+            .nth((position.column.max(1) - 1) as usize)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+
+        start + column
+    }
+
+    /// Returns the span starting at the position that covers the provided number of characters
+    pub fn span(&self, position: FilePosition, length: u32) -> Span {
+        let start = self.offset(position);
+
+        let end = self.content[start..]
+            .char_indices()
+            .nth(length as usize)
+            .map(|(i, _)| start + i)
+            .unwrap_or(self.content.len());
+
+        Span::new(start, end)
+    }
+
+    /// Returns the line and column of the byte offset
+    pub fn position(&self, offset: usize) -> FilePosition {
+        let offset = offset.min(self.content.len());
+        let line = self.starts.partition_point(|start| *start <= offset);
+        let start = self.starts[line - 1];
+
+        FilePosition {
+            line: line as u32,
+            column: self.content[start..offset].chars().count() as u32 + 1,
+        }
+    }
+
+    /// Returns the line (0 based) and the column in UTF-16 code units used by the language server protocol
+    pub fn lsp_position(&self, offset: usize) -> (u32, u32) {
+        let offset = offset.min(self.content.len());
+        let line = self.starts.partition_point(|start| *start <= offset);
+        let start = self.starts[line - 1];
+
+        let character: usize = self.content[start..offset].chars().map(|c| c.len_utf16()).sum();
+
+        ((line - 1) as u32, character as u32)
+    }
+}
+
+/// Collects the diagnostics of the parse result for the provided environment
+///
+/// Keys that are not included in the environment are not reported.
+pub fn collect(result: &ParseResult, environment: &Environment) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = result
+        .errors
+        .iter()
+        .chain(result.warnings.iter())
+        .cloned()
+        .collect();
+
+    // Keys that aren't part of the environment or whose `@when` conditions aren't met are skipped
+    for (key, active) in result.keys.iter().zip(result.active(environment)) {
+        if active {
+            diagnostics.extend(key.errors.iter().cloned());
+        }
+    }
+
+    diagnostics.sort_by_key(|d| d.span.start);
+
+    diagnostics
+}
+
+/// Returns true if any of the diagnostics is an error
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|d| d.is_error())
+}
@@ -0,0 +1,416 @@
+use crate::decorators::cross_key;
+use crate::diagnostics::{Diagnostic, LineIndex, Severity, Span};
+use crate::parsing::{config::Cloak, Key};
+use crate::util;
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+/// The output formats diagnostics can be rendered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Colored output with the source and squiggles for people
+    Terminal,
+    /// The diagnostics as JSON including their spans, labels and fixes
+    Json,
+    /// Diagnostics as defined by the language server protocol
+    Lsp,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "terminal" | "text" => Ok(Format::Terminal),
+            "json" => Ok(Format::Json),
+            "lsp" => Ok(Format::Lsp),
+            _ => Err(format!(
+                "'{s}' is not a valid format. Expected one of 'terminal', 'json' or 'lsp'."
+            )),
+        }
+    }
+}
+
+/// Hides the value, showing its first and last `reveal` characters when most of it stays hidden
+///
+/// The quotes around strings are kept when part of the value is shown.
+///
+/// # Examples
+/// ```
+/// use vnv::diagnostics::mask;
+///
+/// assert_eq!(mask("\"secret\"", 0), "********");
+/// assert_eq!(mask("sk_live_1234", 2), "sk********34");
+/// assert_eq!(mask("\"abc\"", 2), "\"***\"");
+/// ```
+pub fn mask(value: &str, reveal: usize) -> String {
+    if reveal == 0 {
+        return "*".repeat(value.chars().count());
+    }
+
+    let quoted = value.len() >= 2 && value.starts_with('"') && value.ends_with('"');
+
+    let inner = if quoted { &value[1..value.len() - 1] } else { value };
+    let chars: Vec<char> = inner.chars().collect();
+
+    // Values too short to hide most of are hidden entirely
+    let masked: String = if chars.len() < reveal * 4 {
+        "*".repeat(chars.len())
+    } else {
+        chars
+            .iter()
+            .enumerate()
+            .map(|(i, c)| if i < reveal || i >= chars.len() - reveal { *c } else { '*' })
+            .collect()
+    };
+
+    if quoted {
+        return format!("\"{masked}\"");
+    }
+
+    masked
+}
+
+/// Replaces the copies of the value in the text that aren't part of a longer word or number
+fn replace_value(text: &str, value: &str, replacement: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    let mut result = String::new();
+    let mut last = 0;
+
+    for (i, _) in text.match_indices(value) {
+        let end = i + value.len();
+
+        let before = text[..i].chars().next_back().is_some_and(is_word);
+        let after = text[end..].chars().next().is_some_and(is_word);
+
+        if i < last || before || after {
+            continue;
+        }
+
+        result.push_str(&text[last..i]);
+        result.push_str(replacement);
+        last = end;
+    }
+
+    result.push_str(&text[last..]);
+
+    result
+}
+
+/// A span of the source that is hidden in the output
+struct Redaction {
+    /// The key the value belongs to, `None` hides the span in every message
+    key: Option<String>,
+    span: Span,
+}
+
+/// Renders diagnostics for the source they were found in
+pub struct Renderer<'a> {
+    index: LineIndex<'a>,
+    file: &'a str,
+    redactions: Vec<Redaction>,
+    /// The key, decorator and referenced key of each cross-key decorator in the file
+    references: Vec<(String, String, String)>,
+    /// The number of characters shown at the start and end of hidden values
+    reveal: usize,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(source: &'a str, file: &'a str) -> Self {
+        Renderer {
+            index: LineIndex::new(source),
+            file,
+            redactions: Vec::new(),
+            references: Vec::new(),
+            reveal: 0,
+        }
+    }
+
+    /// Hides the spans of the source in the output along with any copies of them in messages
+    pub fn redact(mut self, spans: Vec<Span>) -> Self {
+        self.redactions = spans.into_iter().map(|span| Redaction { key: None, span }).collect();
+        self
+    }
+
+    /// Hides the values of the keys that are cloaked, showing the first and last `reveal` characters
+    pub fn cloak(mut self, keys: &[Key], cloak: Cloak, reveal: usize) -> Self {
+        self.redactions = keys
+            .iter()
+            .filter(|k| cloak.hides(k))
+            // The items are hidden on their own as well since messages about an array show a single item
+            .flat_map(|k| {
+                std::iter::once(k.value_span)
+                    .chain(k.item_spans.iter().copied())
+                    .map(|span| Redaction { key: Some(k.key.to_owned()), span })
+            })
+            .collect();
+        self.references = keys
+            .iter()
+            .flat_map(|k| {
+                k.decorators.iter().flat_map(|d| {
+                    cross_key::references(d)
+                        .into_iter()
+                        .map(|other| (k.key.to_owned(), d.key.to_owned(), other))
+                })
+            })
+            .collect();
+        self.reveal = reveal;
+        self
+    }
+
+    /// Hides the redacted values in every part of the diagnostic that is shown
+    fn redact_diagnostic(&self, diagnostic: &Diagnostic) -> Diagnostic {
+        let mut d = diagnostic.to_owned();
+
+        d.message = self.redact_message(diagnostic, &d.message);
+
+        for label in &mut d.labels {
+            label.message = self.redact_message(diagnostic, &label.message);
+        }
+
+        if let Some(fix) = &mut d.fix {
+            fix.message = self.redact_message(diagnostic, &fix.message);
+
+            // Fixes that replace a hidden value would show it
+            if self
+                .redactions
+                .iter()
+                .any(|r| r.span.start < fix.span.end && fix.span.start < r.span.end)
+            {
+                fix.replacement = mask(&fix.replacement, self.reveal);
+            }
+        }
+
+        d
+    }
+
+    pub fn render(&self, format: Format, diagnostics: &[Diagnostic]) -> String {
+        match format {
+            Format::Terminal => diagnostics
+                .iter()
+                .map(|d| self.terminal(d))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            Format::Json => self.json(diagnostics),
+            Format::Lsp => self.lsp(diagnostics),
+        }
+    }
+
+    /// Replaces the redacted parts of the line with `*`
+    fn redact_line(&self, line: u32) -> String {
+        let start = self.index.offset(crate::parsing::FilePosition { line, column: 1 });
+
+        self.index
+            .line(line)
+            .char_indices()
+            .map(|(i, c)| {
+                let offset = start + i;
+
+                let redaction = self
+                    .redactions
+                    .iter()
+                    .find(|r| r.span.start <= offset && offset < r.span.end);
+
+                match redaction {
+                    Some(r) => {
+                        // The character at the same position of the masked value
+                        let position = self.index.text(Span::new(r.span.start, offset)).chars().count();
+
+                        mask(self.index.text(r.span), self.reveal)
+                            .chars()
+                            .nth(position)
+                            .unwrap_or('*')
+                    }
+                    None => c,
+                }
+            })
+            .collect()
+    }
+
+    /// Replaces copies of the redacted values in a message
+    ///
+    /// Only the values of the diagnostic's key and the keys its decorator references are replaced so numbers
+    /// in unrelated messages are left alone. Diagnostics that aren't about a key hide every value.
+    fn redact_message(&self, diagnostic: &Diagnostic, message: &str) -> String {
+        let mut message = message.to_owned();
+
+        for redaction in self.redactions.iter().filter(|r| self.concerns(diagnostic, r)) {
+            let text = util::trim_quotes(self.index.text(redaction.span));
+
+            if !text.is_empty() {
+                message = replace_value(&message, &text, &mask(&text, self.reveal));
+            }
+        }
+
+        message
+    }
+
+    /// Returns true if the message of the diagnostic can include the redacted value
+    fn concerns(&self, diagnostic: &Diagnostic, redaction: &Redaction) -> bool {
+        let (Some(key), Some(owner)) = (&diagnostic.key, &redaction.key) else {
+            return true;
+        };
+
+        return key == owner
+            || self
+                .references
+                .iter()
+                .any(|(k, decorator, other)| k == key && *decorator == diagnostic.code && other == owner);
+    }
+
+    /// Creates the row under a line that marks the span with the provided character
+    fn underline(&self, line: u32, span: Span, marker: char) -> String {
+        let start = self.index.position(span.start);
+        let end = self.index.position(span.end);
+
+        let from = if start.line < line { 1 } else { start.column };
+        let to = if end.line > line {
+            self.index.line(line).chars().count() as u32 + 1
+        } else {
+            end.column
+        };
+
+        let mut row = " ".repeat(from as usize - 1);
+        row.push_str(&marker.to_string().repeat((to.saturating_sub(from)).max(1) as usize));
+
+        row
+    }
+
+    /// Renders a diagnostic with the lines of source it points to
+    pub fn terminal(&self, diagnostic: &Diagnostic) -> String {
+        let diagnostic = &self.redact_diagnostic(diagnostic);
+
+        let header = match diagnostic.severity {
+            Severity::Error => format!("ERROR[{}]", diagnostic.code).red().bold(),
+            Severity::Warning => format!("WARN[{}]", diagnostic.code).bright_yellow().bold(),
+            Severity::Info => format!("INFO[{}]", diagnostic.code).blue().bold(),
+        };
+        let ascii_line = "|".blue();
+        let ascii_arrow = "-->".blue();
+
+        // Every line that is marked by the diagnostic or one of its labels
+        let mut marks: Vec<(u32, Span, char, String)> = Vec::new();
+
+        let primary_line = diagnostic.position.line;
+        marks.push((primary_line, diagnostic.span, '^', String::new()));
+
+        for label in &diagnostic.labels {
+            let line = self.index.position(label.span.start).line;
+            marks.push((line, label.span, '-', label.message.to_owned()));
+        }
+
+        let mut lines: Vec<u32> = marks.iter().map(|(line, _, _, _)| *line).collect();
+        lines.sort();
+        lines.dedup();
+
+        // The gutter is as wide as the largest line number so the bars stay aligned past line 99
+        let width = lines.last().unwrap_or(&primary_line).to_string().len().max(2);
+        let gutter = " ".repeat(width + 3);
+
+        let mut output = format!(
+            "{header}: {}\n{ascii_arrow} {}:{}:{}\n{gutter}{ascii_line}\n",
+            diagnostic.message.bold(),
+            self.file,
+            diagnostic.position.line,
+            diagnostic.position.column,
+        );
+
+        for line in lines {
+            output.push_str(&format!(
+                "{}   {ascii_line}  {}\n",
+                util::number_pad(line, width).to_string().blue(),
+                self.redact_line(line)
+            ));
+
+            for (_, span, marker, message) in marks.iter().filter(|(l, _, _, _)| *l == line) {
+                let underline = self.underline(line, *span, *marker);
+
+                let row = if *marker == '^' {
+                    underline.red().to_string()
+                } else {
+                    format!("{underline} {message}").blue().to_string()
+                };
+
+                output.push_str(&format!("{gutter}{ascii_line}  {row}\n"));
+            }
+        }
+
+        if let Some(fix) = &diagnostic.fix {
+            output.push_str(&format!("{gutter}{} {}\n", "= help:".blue().bold(), fix.message));
+        }
+
+        output
+    }
+
+    /// Renders the diagnostics as a JSON object
+    pub fn json(&self, diagnostics: &[Diagnostic]) -> String {
+        let diagnostics: Vec<Diagnostic> = diagnostics.iter().map(|d| self.redact_diagnostic(d)).collect();
+
+        let output = json!({
+            "file": self.file,
+            "diagnostics": diagnostics,
+        });
+
+        serde_json::to_string_pretty(&output).unwrap()
+    }
+
+    fn lsp_range(&self, span: Span) -> Value {
+        let (start_line, start_character) = self.index.lsp_position(span.start);
+        let (end_line, end_character) = self.index.lsp_position(span.end);
+
+        json!({
+            "start": { "line": start_line, "character": start_character },
+            "end": { "line": end_line, "character": end_character },
+        })
+    }
+
+    /// Renders the diagnostics as language server protocol diagnostics
+    ///
+    /// Fixes are included in the `data` of the diagnostic so they can be offered as code actions.
+    pub fn lsp(&self, diagnostics: &[Diagnostic]) -> String {
+        let output: Vec<Value> = diagnostics
+            .iter()
+            .map(|d| self.redact_diagnostic(d))
+            .map(|d| {
+                let severity = match d.severity {
+                    Severity::Error => 1,
+                    Severity::Warning => 2,
+                    Severity::Info => 3,
+                };
+
+                let related: Vec<Value> = d
+                    .labels
+                    .iter()
+                    .map(|label| {
+                        json!({
+                            "location": { "uri": self.file, "range": self.lsp_range(label.span) },
+                            "message": label.message,
+                        })
+                    })
+                    .collect();
+
+                let fix = d.fix.as_ref().map(|fix| {
+                    json!({
+                        "title": fix.message,
+                        "range": self.lsp_range(fix.span),
+                        "newText": fix.replacement,
+                    })
+                });
+
+                json!({
+                    "range": self.lsp_range(d.span),
+                    "severity": severity,
+                    "code": d.code,
+                    "source": "vnv",
+                    "message": d.message,
+                    "relatedInformation": related,
+                    "data": { "fix": fix },
+                })
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&output).unwrap()
+    }
+}
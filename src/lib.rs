@@ -1,11 +1,32 @@
+#![allow(clippy::needless_return)]
+
+pub mod de;
 pub mod decorators;
+pub mod diagnostics;
+pub mod loader;
 pub mod util;
 pub mod parsing;
 
+pub use de::from_str;
+pub use diagnostics::Diagnostic;
+pub use loader::{load, load_str, LoadedEnv};
+pub use parsing::{Environment, Environment as Env};
+
 #[cfg(test)]
 mod tests {
+    mod common;
     mod value_types;
     mod util;
+    mod environments;
+    mod units;
+    mod secrets;
+    mod loader;
+    mod de;
+    mod diagnostics;
+    mod syntax;
+    mod naming;
+    mod config;
+    mod files;
     mod decorators {
         mod min;
         mod max;
@@ -15,5 +36,28 @@ mod tests {
         mod starts_with;
         mod matches;
         mod does_not_match;
+        mod gte;
+        mod required_if;
+        mod one_of_keys;
+        mod same_length_as;
+        mod one_of;
+        mod not_one_of;
+        mod arguments;
+        mod min_items;
+        mod max_items;
+        mod integer;
+        mod duration;
+        mod bytes;
+        mod percent;
+        mod entropy;
+        mod not_default;
+        mod not_in;
+        mod non_empty;
+        mod unique_items;
+        mod sorted;
+        mod documentation;
+        mod alias;
+        mod sensitive;
+        mod when;
     }
 }
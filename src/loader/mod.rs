@@ -0,0 +1,144 @@
+use crate::diagnostics::{self, Diagnostic, LineIndex};
+use crate::parsing::{self, Environment, Key, ValueType};
+use std::{env, fs, path::Path};
+
+/// The validated keys of a .vnv file for a single environment
+#[derive(Debug, Clone)]
+pub struct LoadedEnv {
+    pub environment: Environment,
+    pub keys: Vec<Key>,
+    /// Warnings found while loading like deprecated keys or keys that are set under a previous name
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl LoadedEnv {
+    /// Returns the value of the key
+    pub fn get(&self, key: &str) -> Option<&ValueType> {
+        self.keys.iter().find(|k| k.key == key).map(|k| &k.value)
+    }
+
+    /// Returns the keys and their values as they would be read from the environment
+    pub fn vars(&self) -> Vec<(String, String)> {
+        self.keys
+            .iter()
+            .map(|k| (k.key.to_owned(), k.value.env_value()))
+            .collect()
+    }
+
+    /// Sets the keys in the environment of the current process
+    ///
+    /// Variables that are already set are not overridden so the real environment takes precedence.
+    /// A key that is only set under a previous name from its `@alias` decorator takes the value of the previous name.
+    pub fn set_env(&self) {
+        for key in &self.keys {
+            if env::var_os(&key.key).is_some() {
+                continue;
+            }
+
+            let value = key
+                .aliases()
+                .iter()
+                .find_map(env::var_os)
+                .unwrap_or(key.value.env_value().into());
+
+            env::set_var(&key.key, value);
+        }
+    }
+
+    /// Sets the keys in the environment of the current process overriding any that are already set
+    pub fn set_env_override(&self) {
+        for (key, value) in self.vars() {
+            env::set_var(key, value);
+        }
+    }
+}
+
+/// Parses and validates the .vnv content for the provided environment
+///
+/// Keys that aren't set in the environment of the current process but are set under a previous name
+/// from their `@alias` decorator are accepted with a warning.
+///
+/// # Examples
+/// ```
+/// use vnv::Environment;
+///
+/// let loaded = vnv::load_str("@min(1024)\nPORT=3000", Environment::Prod).unwrap();
+///
+/// assert_eq!(loaded.vars(), vec![("PORT".to_owned(), "3000".to_owned())]);
+///
+/// let diagnostics = vnv::load_str("@min(1024)\nPORT=80", Environment::Prod).unwrap_err();
+///
+/// assert_eq!(diagnostics[0].message, "80 is too small. Minimum value is 1024.");
+/// ```
+pub fn load_str(content: &str, environment: Environment) -> Result<LoadedEnv, Vec<Diagnostic>> {
+    let result = parsing::parse(content);
+
+    let diagnostics = diagnostics::collect(&result, &environment);
+
+    if diagnostics::has_errors(&diagnostics) {
+        return Err(diagnostics);
+    }
+
+    let keys = result.resolve(&environment);
+
+    let mut warnings: Vec<Diagnostic> = diagnostics.into_iter().filter(|d| !d.is_error()).collect();
+
+    let index = LineIndex::new(content);
+
+    for key in &keys {
+        if env::var_os(&key.key).is_some() {
+            continue;
+        }
+
+        if let Some(alias) = key.aliases().into_iter().find(|alias| env::var_os(alias).is_some()) {
+            let message = format!(
+                "'{alias}' was renamed to '{}'. The value of '{alias}' is used until it is renamed in the environment.",
+                key.key
+            );
+
+            warnings.push(
+                Diagnostic::warning("renamed-key", &message)
+                    .at(&index, key.position, key.key.chars().count() as u32)
+                    .for_key(&key.key),
+            );
+        }
+    }
+
+    Ok(LoadedEnv {
+        keys,
+        environment,
+        warnings,
+    })
+}
+
+/// Reads, parses and validates the .vnv file for the provided environment
+///
+/// Call [`LoadedEnv::set_env`] on the result to populate the environment of the current process.
+///
+/// ```no_run
+/// use vnv::Environment;
+///
+/// match vnv::load(".vnv", Environment::Prod) {
+///     Ok(loaded) => loaded.set_env(),
+///     Err(diagnostics) => {
+///         for diagnostic in diagnostics {
+///             eprintln!("{diagnostic}");
+///         }
+///         std::process::exit(1);
+///     }
+/// }
+/// ```
+pub fn load(path: impl AsRef<Path>, environment: Environment) -> Result<LoadedEnv, Vec<Diagnostic>> {
+    let path = path.as_ref();
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => {
+            let message = format!("Couldn't read '{}': {err}", path.display());
+
+            return Err(vec![Diagnostic::error("io", &message)]);
+        }
+    };
+
+    load_str(&content, environment)
+}
@@ -1,9 +1,15 @@
+#![allow(clippy::needless_return)]
+
 use clap::Parser;
+use std::path::PathBuf;
 use colored::Colorize;
-use vnv::parsing::{config, Environment};
+use vnv::{
+    diagnostics::Format,
+    parsing::{config, Environment},
+};
 mod commands;
 
-use commands::{build, check, Commands};
+use commands::{audit, build, check, diff, docs, Commands};
 
 #[derive(Parser, Debug)]
 #[command(version, about, author, long_about = None)]
@@ -11,52 +17,159 @@ struct Cli {
     /// Commands to execute
     #[clap(subcommand)]
     command: Commands,
+
+    /// Path of the config file, defaults to the first config found from the current directory upwards.
+    #[clap(long, value_parser, global = true)]
+    config: Option<PathBuf>,
+
+    #[clap(flatten)]
+    overrides: commands::config::Overrides,
 }
 
-pub const CONFIG_PATH: &str = ".vnv.config.json";
+pub const CONFIG_PATH: &str = config::JSON_FILE;
 
 fn main() {
     let args = Cli::parse();
 
-    let mut config = config::parse(CONFIG_PATH);
+    if let Commands::Init {} = args.command {
+        commands::init();
+        return;
+    }
+
+    let mut flags = args.overrides.flags();
+
+    // `vnv build --normalize` and `--aliases` are shorthands for the overrides so they are tracked the same way,
+    // `--build-normalize` and `--build-aliases` win if both are passed
+    if let Commands::Build { normalize, aliases, .. } = args.command {
+        if normalize {
+            flags.insert(0, ("build.normalize", "true".to_owned()));
+        }
+
+        if aliases {
+            flags.insert(0, ("build.aliases", "true".to_owned()));
+        }
+    }
+
+    // Flags take precedence over VNV_* environment variables which take precedence over the config file
+    let resolved = match config::resolve(
+        args.config.as_deref(),
+        |name| std::env::var(name).ok(),
+        &flags,
+    ) {
+        Ok(resolved) => resolved,
+        Err(err) => {
+            eprintln!("{} {err}", "ERROR:".bold().red());
+            std::process::exit(1);
+        }
+    };
+
+    let mut config = resolved.options.clone();
 
     let mut environment: Environment = Environment::Dev;
 
     match args.command {
         Commands::Check {
             file,
-            cloak,
             dev,
-            prod
+            prod,
+            format,
         } => {
             // Overrides config with passed arguments
             if let Some(file) = file {
                 config.src = file;
             }
 
-            if cloak {
-                config.cloak = true;
-            }
-
             if dev && prod {
                 println!("{} You provided multiple environment flags (--dev, --prod) defaulting to the development environment", "WARN:".bold().bright_yellow())
             } else if prod {
                 environment = Environment::Prod;
             }
 
-            let options = check::Options { config, environment };
+            let options = check::Options {
+                config,
+                environment,
+                format: format.unwrap_or(Format::Terminal),
+                content: None,
+                stderr: false,
+            };
             commands::check(options);
         }
-        Commands::Build { dev, prod } => {
+        Commands::Build {
+            dev,
+            prod,
+            check,
+            target,
+            stdout,
+            ..
+        } => {
+            // Warnings go to stderr so they don't end up in the build written to stdout
             if dev && prod {
-                println!("{} You provided multiple environment flags (--dev, --prod) defaulting to the development environment", "WARN:".bold().bright_yellow())
+                eprintln!("{} You provided multiple environment flags (--dev, --prod) defaulting to the development environment", "WARN:".bold().bright_yellow())
             } else if prod {
                 environment = Environment::Prod;
             }
-            
-            let options = build::Options { config, environment };
+
+            let options = build::Options {
+                config,
+                environment,
+                check,
+                target,
+                stdout,
+            };
             commands::build(options);
         }
-        Commands::Init {} => commands::init(),
+        // Init runs before the config is loaded since it creates it
+        Commands::Init {} => unreachable!(),
+        Commands::Diff {
+            env,
+            file,
+            fail_on_missing,
+        } => {
+            if let Some(file) = file {
+                config.src = file;
+            }
+
+            let (left, right) = match env.as_slice() {
+                [] => (Environment::Dev, Environment::Prod),
+                [left, right] => (left.to_owned(), right.to_owned()),
+                _ => {
+                    println!("{} diff requires exactly two environments. Ex: `vnv diff --env dev --env prod`", "ERROR:".bold().red());
+                    std::process::exit(1);
+                }
+            };
+
+            let options = diff::Options {
+                config,
+                left,
+                right,
+                fail_on_missing,
+            };
+            commands::diff(options);
+        }
+        Commands::Audit { file } => {
+            if let Some(file) = file {
+                config.src = file;
+            }
+
+            commands::audit(audit::Options { config });
+        }
+        Commands::Docs {
+            file,
+            format,
+            output,
+        } => {
+            if let Some(file) = file {
+                config.src = file;
+            }
+
+            commands::docs(docs::Options {
+                config,
+                format: format.unwrap_or(docs::DocsFormat::Markdown),
+                output,
+            });
+        }
+        Commands::Config { action } => {
+            commands::config(commands::config::Options { resolved, action });
+        }
     }
 }
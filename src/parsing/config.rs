@@ -1,19 +1,282 @@
-use std::fs;
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::parsing::{Environment, Key, Scope};
+use crate::util::{closest_match, glob_match};
+
+/// The name of the JSON config file
+pub const JSON_FILE: &str = ".vnv.config.json";
+/// The name of the TOML config file
+pub const TOML_FILE: &str = "vnv.toml";
+/// The name of the Cargo manifest that can hold the config in `[package.metadata.vnv]`
+pub const CARGO_FILE: &str = "Cargo.toml";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Options {
     pub src: String,
-    pub cloak: bool,
+    pub cloak: Cloak,
+    /// Shows the first and last characters of cloaked values, they are fully hidden when 0
+    pub cloak_reveal: usize,
     pub build: Build,
+    pub naming: Naming,
+}
+
+/// The keys whose values are hidden in the output, keys with `@sensitive` are always hidden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Cloak {
+    #[default]
+    Off,
+    /// Hides every value
+    All,
+    /// Hides the values of private keys and shows public keys
+    Private,
+}
+
+impl Cloak {
+    /// Returns true if the value of the key is hidden in the output
+    pub fn hides(&self, key: &Key) -> bool {
+        match self {
+            Cloak::Off => key.is_sensitive(),
+            Cloak::All => true,
+            Cloak::Private => key.scope == Scope::Private || key.is_sensitive(),
+        }
+    }
+}
+
+impl fmt::Display for Cloak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cloak::Off => write!(f, "false"),
+            Cloak::All => write!(f, "true"),
+            Cloak::Private => write!(f, "private"),
+        }
+    }
+}
+
+impl FromStr for Cloak {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "private" => Ok(Cloak::Private),
+            "all" => Ok(Cloak::All),
+            "off" => Ok(Cloak::Off),
+            _ => match parse_bool(s) {
+                Ok(true) => Ok(Cloak::All),
+                Ok(false) => Ok(Cloak::Off),
+                Err(_) => Err(format!(
+                    "'{s}' is not a valid cloak. Expected 'true', 'false' or 'private'."
+                )),
+            },
+        }
+    }
+}
+
+// `true` and `false` are kept from when cloak could only hide every value
+impl Serialize for Cloak {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Cloak::Off => serializer.serialize_bool(false),
+            Cloak::All => serializer.serialize_bool(true),
+            Cloak::Private => serializer.serialize_str("private"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Cloak {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = Cloak;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a boolean or \"private\"")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Cloak, E> {
+                match value {
+                    true => Ok(Cloak::All),
+                    false => Ok(Cloak::Off),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Cloak, E> {
+                match value {
+                    "private" => Ok(Cloak::Private),
+                    "all" => Ok(Cloak::All),
+                    "off" => Ok(Cloak::Off),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
 pub struct Build {
     pub output: String,
-    pub minify: bool
+    pub minify: bool,
+    /// Converts values with units (durations, byte sizes and percentages) to their canonical unit
+    pub normalize: bool,
+    /// Also writes keys under the previous names from their `@alias` decorators
+    pub aliases: bool,
+    /// Refuses to overwrite output files that weren't generated by vnv
+    pub protect: bool,
+    /// Files to build from the source, `output` and `minify` are used as the only target when empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<Target>,
+}
+
+impl Default for Build {
+    fn default() -> Self {
+        Build {
+            output: String::from(".env"),
+            minify: false,
+            normalize: false,
+            aliases: false,
+            protect: false,
+            targets: Vec::new(),
+        }
+    }
+}
+
+impl Build {
+    /// Returns the targets to build with their settings resolved
+    pub fn targets(&self) -> Vec<Target> {
+        if self.targets.is_empty() {
+            return vec![Target {
+                name: String::from(DEFAULT_TARGET),
+                output: self.output.to_owned(),
+                minify: Some(self.minify),
+                ..Target::default()
+            }];
+        }
+
+        self.targets
+            .iter()
+            .map(|target| Target {
+                minify: Some(target.minify.unwrap_or(self.minify)),
+                ..target.to_owned()
+            })
+            .collect()
+    }
+}
+
+/// The name of the target built from `build.output` when no targets are configured
+pub const DEFAULT_TARGET: &str = "default";
+
+/// The formats a target can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetFormat {
+    /// `KEY=value` lines
+    #[default]
+    Dotenv,
+    /// An object of the keys and their values
+    Json,
+}
+
+impl fmt::Display for TargetFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetFormat::Dotenv => write!(f, "dotenv"),
+            TargetFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// A file built from the source
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Target {
+    pub name: String,
+    pub output: String,
+    pub format: TargetFormat,
+    /// Environment to build, defaults to the one passed to `vnv build`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+    /// Only includes keys of the scope
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scope>,
+    /// Globs of the keys to include, every key is included when empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Globs of the keys to leave out
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Defaults to `build.minify`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minify: Option<bool>,
+}
+
+impl Target {
+    /// Returns true if the key passes the scope filter and include/exclude globs of the target
+    pub fn includes(&self, key: &Key) -> bool {
+        if let Some(scope) = &self.scope {
+            if &key.scope != scope {
+                return false;
+            }
+        }
+
+        if !self.include.is_empty() && !self.include.iter().any(|glob| glob_match(glob, &key.key)) {
+            return false;
+        }
+
+        !self.exclude.iter().any(|glob| glob_match(glob, &key.key))
+    }
+
+    /// Returns the environment to build, falling back to the one that was passed
+    pub fn environment_or(&self, environment: &Environment) -> Environment {
+        self.environment.to_owned().unwrap_or(environment.to_owned())
+    }
+}
+
+/// Rules for key names on top of the POSIX rules that are always enforced
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Naming {
+    /// Requires keys to be written in SCREAMING_SNAKE_CASE
+    pub screaming_snake_case: bool,
+    /// Prefixes keys are required to start with depending on their scope
+    pub prefixes: Prefixes,
+    /// Names that can't be used as keys (ignoring case)
+    pub forbidden: Vec<String>,
+}
+
+impl Naming {
+    /// Returns true if the name is in the forbidden list
+    pub fn forbids(&self, name: &str) -> bool {
+        self.forbidden.iter().any(|f| f.eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Prefixes {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private: Option<String>,
+}
+
+impl Prefixes {
+    /// Returns the prefix required for keys of the scope
+    pub fn get(&self, scope: &Scope) -> Option<&str> {
+        match scope {
+            Scope::Public => self.public.as_deref(),
+            Scope::Private => self.private.as_deref(),
+        }
+    }
 }
 
 impl Options {
@@ -21,41 +284,484 @@ impl Options {
     pub fn new() -> Self {
         Options {
             src: String::from(".vnv"),
-            cloak: false,
-            build: Build {
-                output: String::from(".env"),
-                minify: false
+            cloak: Cloak::Off,
+            cloak_reveal: 0,
+            build: Build::default(),
+            naming: Naming::default(),
+        }
+    }
+
+    /// Makes the relative paths in the config relative to the directory the config was found in
+    fn relative_to(mut self, dir: &Path) -> Self {
+        if dir.as_os_str().is_empty() || dir == Path::new(".") {
+            return self;
+        }
+
+        let join = |path: &str| {
+            if Path::new(path).is_absolute() {
+                return path.to_owned();
+            }
+
+            dir.join(path).to_string_lossy().to_string()
+        };
+
+        self.src = join(&self.src);
+        self.build.output = join(&self.build.output);
+
+        for target in &mut self.build.targets {
+            target.output = join(&target.output);
+        }
+
+        self
+    }
+
+    /// Sets the field from its text value, lists are separated by commas
+    pub fn set(&mut self, field: &str, value: &str) -> Result<(), String> {
+        match field {
+            "src" => self.src = value.to_owned(),
+            "cloak" => self.cloak = value.parse()?,
+            "cloak_reveal" => {
+                self.cloak_reveal = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("'{value}' is not a valid number of characters."))?
+            }
+            "build.output" => self.build.output = value.to_owned(),
+            "build.minify" => self.build.minify = parse_bool(value)?,
+            "build.normalize" => self.build.normalize = parse_bool(value)?,
+            "build.aliases" => self.build.aliases = parse_bool(value)?,
+            "build.protect" => self.build.protect = parse_bool(value)?,
+            "naming.screaming_snake_case" => self.naming.screaming_snake_case = parse_bool(value)?,
+            "naming.prefixes.public" => self.naming.prefixes.public = parse_prefix(value),
+            "naming.prefixes.private" => self.naming.prefixes.private = parse_prefix(value),
+            "naming.forbidden" => {
+                self.naming.forbidden = value
+                    .split(',')
+                    .map(|name| name.trim().to_owned())
+                    .filter(|name| !name.is_empty())
+                    .collect()
             }
+            _ => return Err(format!("'{field}' is not a config field.")),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of the field formatted for display
+    pub fn get(&self, field: &str) -> Option<String> {
+        let value = match field {
+            "src" => self.src.to_owned(),
+            "cloak" => self.cloak.to_string(),
+            "cloak_reveal" => self.cloak_reveal.to_string(),
+            "build.output" => self.build.output.to_owned(),
+            "build.minify" => self.build.minify.to_string(),
+            "build.normalize" => self.build.normalize.to_string(),
+            "build.aliases" => self.build.aliases.to_string(),
+            "build.protect" => self.build.protect.to_string(),
+            "naming.screaming_snake_case" => self.naming.screaming_snake_case.to_string(),
+            "naming.prefixes.public" => self.naming.prefixes.public.to_owned().unwrap_or_default(),
+            "naming.prefixes.private" => self.naming.prefixes.private.to_owned().unwrap_or_default(),
+            "naming.forbidden" => self.naming.forbidden.join(","),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where the value of a config field came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    Default,
+    /// The config file at the path
+    File(PathBuf),
+    /// The `VNV_*` environment variable
+    Env(String),
+    /// The command line flag
+    Flag(String),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Default => write!(f, "default"),
+            Origin::File(path) => write!(f, "{}", path.display()),
+            Origin::Env(name) => write!(f, "{name}"),
+            Origin::Flag(flag) => write!(f, "{flag}"),
         }
     }
 }
 
-pub fn parse(path: &str) -> Options {
-    let defaults: Options = Options::new();
+/// An error reading or deserializing a config file, or an invalid override of one of its fields
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    pub origin: Origin,
+    pub message: String,
+}
 
-    let content = fs::read(path);
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.origin {
+            Origin::File(path) => write!(f, "Invalid config '{}': {}", path.display(), self.message),
+            origin => write!(f, "Invalid value for {origin}: {}", self.message),
+        }
+    }
+}
 
-    if let Ok(content) = content {
-        let json = String::from_utf8(content).unwrap();
+impl std::error::Error for ConfigError {}
 
-        let object: Result<Value, _> = serde_json::from_str(&json);
+impl ConfigError {
+    fn new(path: &Path, message: &str) -> Self {
+        ConfigError {
+            origin: Origin::File(path.to_owned()),
+            message: with_suggestion(message),
+        }
+    }
+}
 
-        if let Ok(object) = object {
-            // map the values to the options object
-            return Options {
-                src: object["src"].as_str().unwrap_or(&defaults.src).to_string(),
-                cloak: object["cloak"].to_string().parse().unwrap_or(false),
-                build: Build {
-                    output: object["build"]["output"].as_str().unwrap_or(&defaults.build.output).to_string(),
-                    minify: object["build"]["minify"].to_string().parse().unwrap_or(false)
-                }
-            };
-        } else {
-            return defaults;
+/// The fields of the config that can be overridden, in the order they are shown
+pub const FIELDS: [&str; 12] = [
+    "src",
+    "cloak",
+    "cloak_reveal",
+    "build.output",
+    "build.minify",
+    "build.normalize",
+    "build.aliases",
+    "build.protect",
+    "naming.screaming_snake_case",
+    "naming.prefixes.public",
+    "naming.prefixes.private",
+    "naming.forbidden",
+];
+
+/// Returns the environment variable that overrides the field
+///
+/// # Examples
+/// ```
+/// use vnv::parsing::config::env_var;
+///
+/// assert_eq!(env_var("build.output"), "VNV_BUILD_OUTPUT");
+/// ```
+pub fn env_var(field: &str) -> String {
+    format!("VNV_{}", field.replace('.', "_").to_uppercase())
+}
+
+/// Returns the command line flag that overrides the field
+///
+/// # Examples
+/// ```
+/// use vnv::parsing::config::flag;
+///
+/// assert_eq!(flag("naming.screaming_snake_case"), "--naming-screaming-snake-case");
+/// ```
+pub fn flag(field: &str) -> String {
+    format!("--{}", field.replace(['.', '_'], "-"))
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => Err(format!("'{value}' is not a valid boolean. Expected 'true' or 'false'.")),
+    }
+}
+
+fn parse_prefix(value: &str) -> Option<String> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return None;
+    }
+
+    Some(value.to_owned())
+}
+
+/// Adds a suggestion to serde's unknown field errors when the field looks like a typo
+///
+/// # Examples
+/// ```
+/// use vnv::parsing::config::with_suggestion;
+///
+/// let message = with_suggestion("unknown field `minfy`, expected one of `output`, `minify`");
+///
+/// assert_eq!(message, "unknown field `minfy`, expected one of `output`, `minify`. Did you mean 'minify'?");
+/// ```
+pub fn with_suggestion(message: &str) -> String {
+    let rest = match message.find("unknown field `") {
+        Some(i) => &message[i + "unknown field `".len()..],
+        None => return message.to_owned(),
+    };
+
+    let field = rest.split('`').next().unwrap_or_default();
+
+    // The expected fields are the rest of the quoted names in the message
+    let expected: Vec<String> = rest
+        .split('`')
+        .skip(1)
+        .skip_while(|s| !s.contains("expected"))
+        .skip(1)
+        .step_by(2)
+        .map(|s| s.to_owned())
+        .collect();
+
+    match closest_match(field, &expected) {
+        Some(suggestion) => format!("{}. Did you mean '{suggestion}'?", message.trim_end()),
+        None => message.to_owned(),
+    }
+}
+
+#[derive(Deserialize)]
+struct CargoManifest {
+    package: Option<CargoPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    metadata: Option<CargoMetadata>,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadata {
+    vnv: Option<toml::Value>,
+}
+
+/// Reads the options from the config file
+///
+/// The format is picked from the name of the file. `Cargo.toml` is read from its `[package.metadata.vnv]` table,
+/// other `.toml` files are read as TOML and everything else as JSON. Unknown fields are errors.
+/// Relative paths in the config are relative to the directory of the config file.
+pub fn parse(path: &Path) -> Result<Options, ConfigError> {
+    let (options, _) = read(path)?;
+
+    Ok(options)
+}
+
+/// Reads the options from the config file along with the fields that are set in it
+fn read(path: &Path) -> Result<(Options, Vec<&'static str>), ConfigError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => return Err(ConfigError::new(path, &format!("Couldn't read the file: {err}"))),
+    };
+
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+
+    let (options, value) = if file_name == CARGO_FILE {
+        match cargo_metadata(&content) {
+            Ok(Some(table)) => (
+                table
+                    .clone()
+                    .try_into::<Options>()
+                    .map_err(|err| ConfigError::new(path, &format!("[package.metadata.vnv] {err}")))?,
+                serde_json::to_value(table).unwrap_or_default(),
+            ),
+            Ok(None) => {
+                return Err(ConfigError::new(path, "There is no [package.metadata.vnv] table."))
+            }
+            Err(err) => return Err(ConfigError::new(path, &err.to_string())),
         }
+    } else if file_name.ends_with(".toml") {
+        (
+            toml::from_str::<Options>(&content).map_err(|err| ConfigError::new(path, &err.to_string()))?,
+            toml::from_str::<toml::Value>(&content)
+                .ok()
+                .and_then(|table| serde_json::to_value(table).ok())
+                .unwrap_or_default(),
+        )
     } else {
+        (
+            serde_json::from_str::<Options>(&content)
+                .map_err(|err| ConfigError::new(path, &err.to_string()))?,
+            serde_json::from_str::<serde_json::Value>(&content).unwrap_or_default(),
+        )
+    };
+
+    validate_targets(&options.build.targets).map_err(|message| ConfigError::new(path, &message))?;
+
+    let fields = FIELDS
+        .into_iter()
+        .filter(|field| {
+            let mut current = &value;
 
+            for part in field.split('.') {
+                match current.get(part) {
+                    Some(next) => current = next,
+                    None => return false,
+                }
+            }
+
+            !current.is_null()
+        })
+        .collect();
 
-        return defaults;
+    Ok((options.relative_to(path.parent().unwrap_or(Path::new(""))), fields))
+}
+
+/// Checks that every target has a unique name and an output
+fn validate_targets(targets: &[Target]) -> Result<(), String> {
+    for (i, target) in targets.iter().enumerate() {
+        if target.name.is_empty() {
+            return Err(format!("Build target {} needs a name.", i + 1));
+        }
+
+        if target.output.is_empty() {
+            return Err(format!("Build target '{}' needs an output.", target.name));
+        }
+
+        if targets[..i].iter().any(|t| t.name == target.name) {
+            return Err(format!("There are multiple build targets named '{}'.", target.name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the `[package.metadata.vnv]` table of the Cargo manifest
+fn cargo_metadata(content: &str) -> Result<Option<toml::Value>, toml::de::Error> {
+    let manifest: CargoManifest = toml::from_str(content)?;
+
+    Ok(manifest.package.and_then(|p| p.metadata).and_then(|m| m.vnv))
+}
+
+/// Finds the config by searching the directory and then each of its parents
+///
+/// In each directory `.vnv.config.json` is preferred over `vnv.toml` and a `Cargo.toml` with a `[package.metadata.vnv]` table.
+/// The returned path is relative when `dir` is.
+pub fn find(dir: &Path) -> Option<PathBuf> {
+    let depth = match dir.canonicalize() {
+        Ok(absolute) => absolute.ancestors().count(),
+        Err(_) => 1,
+    };
+
+    let mut current = dir.to_owned();
+
+    for _ in 0..depth {
+        for name in [JSON_FILE, TOML_FILE] {
+            let candidate = current.join(name);
+
+            if candidate.is_file() {
+                return Some(clean(candidate));
+            }
+        }
+
+        let cargo = current.join(CARGO_FILE);
+
+        if let Ok(content) = fs::read_to_string(&cargo) {
+            if let Ok(Some(_)) = cargo_metadata(&content) {
+                return Some(clean(cargo));
+            }
+        }
+
+        current = current.join("..");
+    }
+
+    None
+}
+
+/// Removes the leading `./` from the path
+fn clean(path: PathBuf) -> PathBuf {
+    match path.strip_prefix(".") {
+        Ok(stripped) => stripped.to_owned(),
+        Err(_) => path,
     }
-}
\ No newline at end of file
+}
+
+/// Loads the config from the provided path or the first one found from the current directory upwards
+///
+/// Returns the default options when no config is found along with the path of the config that was used.
+pub fn load(path: Option<&Path>) -> Result<(Options, Option<PathBuf>), ConfigError> {
+    let path = match path {
+        Some(path) => path.to_owned(),
+        None => match find(Path::new(".")) {
+            Some(path) => path,
+            None => return Ok((Options::new(), None)),
+        },
+    };
+
+    let options = parse(&path)?;
+
+    Ok((options, Some(path)))
+}
+
+/// The config after applying the overrides along with where each field came from
+#[derive(Debug, Clone)]
+pub struct Resolved {
+    pub options: Options,
+    /// Path of the config file that was used
+    pub path: Option<PathBuf>,
+    /// The origin of each of the [`FIELDS`] in the same order
+    pub origins: Vec<(&'static str, Origin)>,
+}
+
+impl Resolved {
+    /// Returns where the value of the field came from
+    pub fn origin(&self, field: &str) -> Option<&Origin> {
+        self.origins.iter().find(|(f, _)| *f == field).map(|(_, origin)| origin)
+    }
+}
+
+/// Loads the config and applies the overrides to it
+///
+/// Each field is taken from the first of these that sets it:
+/// 1. `flags`, pairs of a field and its value from the command line
+/// 2. `VNV_*` environment variables, looked up with `env`. Ex: `VNV_BUILD_OUTPUT`
+/// 3. The config file
+/// 4. The defaults
+pub fn resolve(
+    path: Option<&Path>,
+    env: impl Fn(&str) -> Option<String>,
+    flags: &[(&str, String)],
+) -> Result<Resolved, ConfigError> {
+    let path = match path {
+        Some(path) => Some(path.to_owned()),
+        None => find(Path::new(".")),
+    };
+
+    let (mut options, in_file) = match &path {
+        Some(path) => read(path)?,
+        None => (Options::new(), Vec::new()),
+    };
+
+    let mut origins = Vec::new();
+
+    for field in FIELDS {
+        let mut origin = match &path {
+            Some(path) if in_file.contains(&field) => Origin::File(path.to_owned()),
+            _ => Origin::Default,
+        };
+
+        let name = env_var(field);
+
+        if let Some(value) = env(&name) {
+            options.set(field, &value).map_err(|message| ConfigError {
+                origin: Origin::Env(name.to_owned()),
+                message,
+            })?;
+            origin = Origin::Env(name);
+        }
+
+        if let Some((_, value)) = flags.iter().rev().find(|(f, _)| *f == field) {
+            let name = flag(field);
+
+            options.set(field, value).map_err(|message| ConfigError {
+                origin: Origin::Flag(name.to_owned()),
+                message,
+            })?;
+            origin = Origin::Flag(name);
+        }
+
+        origins.push((field, origin));
+    }
+
+    Ok(Resolved {
+        options,
+        path,
+        origins,
+    })
+}
@@ -1,59 +1,143 @@
-use crate::decorators::{self, DecoratorValidationResult, ValidationError};
-use crate::util::trim_quotes;
+use crate::decorators::{self, cross_key, DecoratorParseResult, DecoratorValidationResult, ValidationError};
+use crate::diagnostics::{Diagnostic, LineIndex, Span};
+use crate::util::{closest_match, trim_quotes, Compare, CompareResult};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 pub mod config;
+pub mod naming;
+pub mod units;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct FilePosition {
     pub line: u32,
     pub column: u32,
 }
 
+impl Default for FilePosition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FilePosition {
     pub fn new() -> Self {
         FilePosition { line: 1, column: 1 }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Scope {
     Private,
     Public,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Environment {
     All,
+    #[serde(alias = "development")]
     Dev,
+    #[serde(alias = "production")]
     Prod
 }
 
-impl Environment {
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Environment::All => String::from("all"),
-            Environment::Dev => String::from("dev"),
-            Environment::Prod => String::from("prod"),
+            Environment::All => write!(f, "all"),
+            Environment::Dev => write!(f, "dev"),
+            Environment::Prod => write!(f, "prod"),
+        }
+    }
+}
+
+impl FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "all" => Ok(Environment::All),
+            "dev" | "development" => Ok(Environment::Dev),
+            "prod" | "production" => Ok(Environment::Prod),
+            _ => Err(format!(
+                "'{s}' is not a valid environment. Expected one of 'dev' or 'prod'."
+            )),
         }
-    } 
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum ValueType {
+    Integer(i64),
     Number(f64),
     String(String),
     StringArray(Vec<String>),
+    IntegerArray(Vec<i64>),
     NumberArray(Vec<f64>),
 }
 
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueType::Integer(v) => write!(f, "{v}"),
+            ValueType::Number(v) => write!(f, "{v}"),
+            ValueType::String(v) => write!(f, "\"{v}\""),
+            ValueType::StringArray(v) => write!(f, "{:?}", v),
+            ValueType::IntegerArray(v) => write!(f, "{:?}", v),
+            ValueType::NumberArray(v) => write!(f, "{:?}", v),
+        }
+    }
+}
+
 impl ValueType {
-    pub fn to_string(&self) -> String {
+    /// Compares two number values
+    ///
+    /// Returns `None` if either of the values is not a number.
+    pub fn compare(&self, other: &ValueType) -> Option<CompareResult> {
+        match (self, other) {
+            (ValueType::Integer(a), ValueType::Integer(b)) => Some(Compare::cmp(a, b)),
+            (ValueType::Integer(a), ValueType::Number(b)) => Some(Compare::cmp(a, b)),
+            (ValueType::Number(a), ValueType::Integer(b)) => Some(Compare::cmp(a, b)),
+            (ValueType::Number(a), ValueType::Number(b)) => Some(Compare::cmp(a, b)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as it is read from the environment
+    ///
+    /// Strings are not quoted and arrays are joined with commas.
+    pub fn env_value(&self) -> String {
         match self {
+            ValueType::Integer(v) => v.to_string(),
             ValueType::Number(v) => v.to_string(),
-            ValueType::String(v) => format!("\"{v}\""),
-            ValueType::StringArray(v) => format!("{:?}", v),
-            ValueType::NumberArray(v) => format!("{:?}", v),
+            ValueType::String(v) => v.to_owned(),
+            ValueType::StringArray(v) => v.join(","),
+            ValueType::IntegerArray(v) => v
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+            ValueType::NumberArray(v) => v
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        }
+    }
+
+    /// Returns the name of the type as it is shown to the user
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ValueType::Integer(_) => "integer",
+            ValueType::Number(_) => "number",
+            ValueType::String(_) => "string",
+            ValueType::StringArray(_) => "string[]",
+            ValueType::IntegerArray(_) => "integer[]",
+            ValueType::NumberArray(_) => "number[]",
         }
     }
 }
@@ -65,23 +149,70 @@ pub struct Key {
     pub environment: Environment,
     pub value: ValueType,
     pub position: FilePosition,
+    /// The span of the value in the source
+    pub value_span: Span,
+    /// The span of each element in the source when the value is an array
+    pub item_spans: Vec<Span>,
     pub valid: bool,
-    pub errors: Vec<ValidationError>,
+    /// Diagnostics of the key including warnings like the use of a deprecated key
+    pub errors: Vec<Diagnostic>,
     pub decorators: Vec<decorators::DecoratorParseResult>,
 }
 
-#[derive(Debug, Clone)]
-pub struct ParseError {
-    pub message: String,
-    pub position: FilePosition,
-}
+impl Key {
+    /// Returns true if the key should be included when targeting the provided environment
+    pub fn included_in(&self, environment: &Environment) -> bool {
+        self.environment == Environment::All || self.environment == *environment
+    }
 
-impl ParseError {
-    pub fn new(msg: String, pos: FilePosition) -> Self {
-        ParseError {
-            message: msg,
-            position: pos,
-        }
+    /// Returns the key and the value of each `@when` decorator on the key
+    pub fn conditions(&self) -> Vec<(String, decorators::DecoratorValue)> {
+        self.decorators
+            .iter()
+            .filter(|d| d.key == "when")
+            .filter_map(|d| match (d.arguments.get(0), d.arguments.get(1)) {
+                (Some((_, decorators::DecoratorValue::String(name))), Some((_, value))) => {
+                    Some((name.to_owned(), value.to_owned()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns true if each of the `@when` conditions of the key is met by one of the keys
+    pub fn conditions_met(&self, keys: &[Key]) -> bool {
+        self.conditions().iter().all(|(name, expected)| {
+            keys.iter()
+                .any(|k| &k.key == name && cross_key::value_equals(&k.value, expected))
+        })
+    }
+
+    /// Returns true if the key is decorated with `@sensitive`
+    pub fn is_sensitive(&self) -> bool {
+        self.decorators.iter().any(|d| d.key == "sensitive")
+    }
+
+    /// Returns the previous names of the key from its `@alias` decorators
+    pub fn aliases(&self) -> Vec<String> {
+        self.decorators
+            .iter()
+            .filter(|d| d.key == "alias")
+            .filter_map(|d| match d.arguments.get(0) {
+                Some((_, decorators::DecoratorValue::String(name))) => Some(name.to_owned()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the text passed to a documentation decorator on the key like `@description("...")`
+    pub fn annotation(&self, name: &str) -> Option<String> {
+        self.decorators
+            .iter()
+            .filter(|d| d.key == name)
+            .find_map(|d| match d.arguments.get(0) {
+                Some((_, decorators::DecoratorValue::String(text))) => Some(text.to_owned()),
+                _ => None,
+            })
     }
 }
 
@@ -89,8 +220,8 @@ impl ParseError {
 pub struct ParseResult {
     pub keys: Vec<Key>,
     pub valid: bool,
-    pub errors: Vec<ParseError>,
-    pub warnings: Vec<ParseError>,
+    pub errors: Vec<Diagnostic>,
+    pub warnings: Vec<Diagnostic>,
 }
 
 impl ParseResult {
@@ -102,14 +233,103 @@ impl ParseResult {
             warnings: Vec::new(),
         }
     }
+
+    /// Returns whether each key is part of the provided environment, in the same order as the keys
+    ///
+    /// A key is part of the environment when it is included in it and the conditions of its `@when` decorators
+    /// are met by the resolved keys. Keys whose conditions aren't met are left out until none are left,
+    /// so a key that depends on a left out key is also left out.
+    ///
+    /// # Examples
+    /// ```
+    /// use vnv::parsing::{parse, Environment};
+    ///
+    /// let result = parse("AUTH=\"okta\"\n@when(\"AUTH\", \"okta\")\nOKTA_DOMAIN=\"a.okta.com\"\n@when(\"AUTH\", \"auth0\")\nAUTH0_DOMAIN=\"a.auth0.com\"");
+    ///
+    /// assert_eq!(result.active(&Environment::Dev), vec![true, true, false]);
+    /// ```
+    pub fn active(&self, environment: &Environment) -> Vec<bool> {
+        let mut active: Vec<bool> = self.keys.iter().map(|k| k.included_in(environment)).collect();
+
+        loop {
+            let resolved = self.resolve_active(&active);
+
+            let unmet: Vec<usize> = (0..self.keys.len())
+                .filter(|i| active[*i] && !self.keys[*i].conditions_met(&resolved))
+                .collect();
+
+            if unmet.is_empty() {
+                return active;
+            }
+
+            for i in unmet {
+                active[i] = false;
+            }
+        }
+    }
+
+    /// Returns whether each key is part of the development or production environment, in the same order as the keys
+    ///
+    /// Errors of keys that aren't part of either are never shown so they don't make the result invalid.
+    pub fn active_anywhere(&self) -> Vec<bool> {
+        let dev = self.active(&Environment::Dev);
+        let prod = self.active(&Environment::Prod);
+
+        dev.into_iter().zip(prod).map(|(dev, prod)| dev || prod).collect()
+    }
+
+    /// Resolves the keys that are included in the provided environment
+    ///
+    /// When a key is defined for all environments and also for the provided environment
+    /// the environment specific key takes precedence. Keys whose `@when` conditions aren't met are left out.
+    ///
+    /// # Examples
+    /// ```
+    /// use vnv::parsing::{parse, Environment};
+    ///
+    /// let result = parse("PORT=3000\n@prod\nPORT=8080\n@dev\nDEBUG=1");
+    ///
+    /// let keys = result.resolve(&Environment::Prod);
+    ///
+    /// assert_eq!(keys.len(), 1);
+    /// assert_eq!(keys[0].value.to_string(), "8080");
+    /// ```
+    pub fn resolve(&self, environment: &Environment) -> Vec<Key> {
+        self.resolve_active(&self.active(environment))
+    }
+
+    /// Resolves the keys that are marked as active
+    fn resolve_active(&self, active: &[bool]) -> Vec<Key> {
+        let mut resolved: Vec<Key> = Vec::new();
+
+        for (key, active) in self.keys.iter().zip(active) {
+            if !active {
+                continue;
+            }
+
+            match resolved.iter().position(|k| k.key == key.key) {
+                Some(index) => {
+                    if resolved[index].environment == Environment::All {
+                        resolved[index] = key.to_owned();
+                    }
+                }
+                None => resolved.push(key.to_owned()),
+            }
+        }
+
+        resolved
+    }
 }
 
 pub fn parse(content: &str) -> ParseResult {
     let decorators: HashMap<String, decorators::Decorator> = decorators::get();
+    let cross_key_decorators = decorators::cross_key::get();
 
     let mut result = ParseResult::new();
 
-    let chars: Vec<char> = content.trim().chars().collect();
+    let index = LineIndex::new(content);
+
+    let chars: Vec<char> = content.trim_end().chars().collect();
     let len = chars.len();
 
     let mut position = FilePosition::new();
@@ -117,59 +337,222 @@ pub fn parse(content: &str) -> ParseResult {
     let mut is_value = false;
     let mut is_comment = false;
     let mut is_array = false;
+    // Tracks if the value was written as an array so single item arrays aren't coerced to a single value
+    let mut has_array = false;
     let mut is_decorator = false;
     let mut is_string = false;
+    // Set after a syntax error to ignore the rest of the line
+    let mut skip_line = false;
+    // Set after a syntax error in a key or its value so the key isn't added
+    let mut drop_entry = false;
 
     let mut current_key = (String::new(), FilePosition::new());
+    let mut value_position = FilePosition::new();
     let mut current = String::new();
     let mut current_decorators: Vec<(String, FilePosition)> = Vec::new();
+    let mut decorator_position = FilePosition::new();
+    // Where the last string and array were opened so they can be reported if they are never closed
+    let mut string_open = FilePosition::new();
+    let mut array_open = (0, FilePosition::new());
 
     let mut keys: HashMap<String, Key> = HashMap::new();
 
-    for (i, c) in chars.to_owned().into_iter().enumerate() {
-        if c == '@' && !is_value && !is_comment && !is_decorator {
+    let mut i = 0;
+
+    while i < len {
+        let c = chars[i];
+
+        if skip_line {
+            // ignored until the next line
+        } else if c == '@' && !is_value && !is_comment && !is_decorator {
             is_decorator = true;
+            decorator_position = position.to_owned();
         } else if c == '=' && !is_decorator && !is_array && !is_value && !is_comment && !is_string {
             is_value = true;
+            // Whitespace around the key (`PORT = 3000`) isn't part of it
+            let leading = current.chars().take_while(|c| c.is_whitespace()).count() as u32;
+
             let mut key_position = position.to_owned();
             key_position.column = key_position
                 .column
-                .checked_sub(current.len() as u32)
+                .checked_sub(current.chars().count() as u32)
                 .unwrap();
-            current_key = (current.clone(), key_position);
+
+            if let Some(error_message) = invalid_key_name(&current) {
+                let key_span = trimmed_span(&index, index.offset(key_position), index.offset(position));
+                let key_span = if key_span.start == key_span.end {
+                    index.span(position, 1)
+                } else {
+                    key_span
+                };
+
+                result
+                    .errors
+                    .push(Diagnostic::error("invalid-key", &error_message).spanning(&index, key_span));
+                drop_entry = true;
+            }
+
+            current_key = (
+                current.trim().to_owned(),
+                FilePosition {
+                    line: key_position.line,
+                    column: key_position.column + leading,
+                },
+            );
             current = String::new();
+            value_position = FilePosition {
+                line: position.line,
+                column: position.column + 1,
+            };
         } else if c == '"' && is_value {
             is_string = !is_string;
-            current.push_str(&c.to_string());
+            string_open = position;
+            current.push(c);
         } else if c == '[' && is_value && !is_array && !is_string {
             is_array = true;
+            has_array = true;
+            array_open = (i, position);
         } else if c == ']' && is_value && is_array && !is_string {
             is_array = false;
+        } else if c == ']' && !is_array && !is_string && !is_comment && !is_decorator {
+            result.errors.push(
+                Diagnostic::error("unexpected-bracket", "Unexpected ']'. There is no array to close.")
+                    .at(&index, position, 1),
+            );
+            skip_line = true;
         } else if c == '#' && !is_value && !is_array && !is_string {
             is_comment = true;
         } else if !is_comment && !((c == '\n' || c == '\r') && !is_string) {
-            current.push_str(&c.to_string());
+            current.push(c);
+        }
+
+        // Strings end with the line they are opened on
+        if is_string && (c == '\n' || i == len - 1) && !skip_line {
+            result.errors.push(
+                Diagnostic::error("unterminated-string", "Unterminated string. Expected a closing '\"'.")
+                    .at(&index, string_open, 1),
+            );
+            is_string = false;
+            drop_entry = true;
+        }
+
+        // Arrays that are never closed would swallow the rest of the file. They are reported when the
+        // file ends or a later line looks like a key and parsing starts again from the line after they were opened.
+        let looks_like_key = c == '=' && !is_string && position.line > array_open.1.line;
+
+        if is_value && is_array && !skip_line && (i == len - 1 || looks_like_key) {
+            let (open, open_position) = array_open;
+
+            result.errors.push(
+                Diagnostic::error("unterminated-array", "Unterminated array. Expected a closing ']'.")
+                    .at(&index, open_position, 1)
+                    .with_label(
+                        index.span(current_key.1, current_key.0.chars().count() as u32),
+                        "value of this key",
+                    ),
+            );
+
+            match chars[open..].iter().position(|c| *c == '\n') {
+                Some(n) => {
+                    i = open + n + 1;
+                    position = FilePosition {
+                        line: open_position.line + 1,
+                        column: 1,
+                    };
+                }
+                None => break,
+            }
+
+            current_key = (String::new(), FilePosition::new());
+            current_decorators = Vec::new();
+            current = String::new();
+            is_value = false;
+            is_array = false;
+            has_array = false;
+            is_string = false;
+            drop_entry = false;
+            continue;
         }
 
         if (c == '\n' && !is_string && !is_array) || i == len - 1 {
-            if is_decorator {
-                current_decorators.push((current.trim().to_owned(), position.to_owned()));
+            if !is_decorator && !is_value && !skip_line && !current.trim().is_empty() {
+                let line_start = index.offset(FilePosition {
+                    line: position.line,
+                    column: 1,
+                });
+                let line_span = trimmed_span(&index, line_start, line_start + current.len());
+                let error_message = format!("Expected '=' after '{}'.", current.trim());
+
+                result
+                    .errors
+                    .push(Diagnostic::error("missing-equals", &error_message).spanning(&index, line_span));
+            }
+
+            if skip_line || (is_value && drop_entry) {
+                // Drops the key along with its decorators
+                current_key = (String::new(), FilePosition::new());
+                current_decorators = Vec::new();
+                is_value = false;
+                is_array = false;
+                has_array = false;
+                is_string = false;
+                skip_line = false;
+                drop_entry = false;
+                is_comment = false;
+            } else if is_decorator {
+                current_decorators.push((current.trim().to_owned(), decorator_position.to_owned()));
                 is_decorator = false;
             } else if is_comment {
                 is_comment = false;
             } else if is_value {
+                let value_end = if c == '\n' {
+                    index.offset(position)
+                } else {
+                    index.offset(position) + c.len_utf8()
+                };
+                let value_span = trimmed_span(&index, index.offset(value_position), value_end);
+                let item_spans = if has_array {
+                    array_item_spans(&index, value_span)
+                } else {
+                    Vec::new()
+                };
+
                 let mut scope = Scope::Private;
                 let mut environment = Environment::All;
 
-                let value_type = coerce_value_type(&current.trim());
+                let value_type = if has_array {
+                    coerce_array_value_type(current.trim())
+                } else {
+                    coerce_value_type(current.trim())
+                };
 
-                let mut errors: Vec<ValidationError> = Vec::new();
+                let mut errors: Vec<Diagnostic> = Vec::new();
 
                 let mut constraints: Vec<decorators::DecoratorParseResult> = Vec::new();
 
                 // Validate with decorators
                 for (dec, pos) in current_decorators {
-                    let decorator_info = decorators::parse(&dec);
+                    // Gets the position of a character in the decorator (after the `@`)
+                    let position_of = |offset: usize| FilePosition {
+                        line: pos.line,
+                        column: pos.column + 1 + offset as u32,
+                    };
+
+                    let mut decorator_info = match decorators::parse(&dec) {
+                        Ok(decorator_info) => decorator_info,
+                        Err(err) => {
+                            result.errors.push(
+                                Diagnostic::error("invalid-syntax", &err.message).at(
+                                    &index,
+                                    position_of(err.offset),
+                                    err.length as u32,
+                                ),
+                            );
+                            continue;
+                        }
+                    };
+
+                    decorator_info.locate(&index, pos, dec.chars().count());
 
                     let found_decorator = decorators.get(&decorator_info.key);
 
@@ -177,11 +560,31 @@ pub fn parse(content: &str) -> ParseResult {
                         Some(d) => {
                             constraints.push(decorator_info.to_owned());
 
-                            let result = (d.validator)(value_type.to_owned(), decorator_info.value);
+                            let validation = d.validate(value_type.to_owned(), &decorator_info.arguments);
+
+                            if let DecoratorValidationResult::Error(errs) = validation {
+                                for err in locate_items(&value_type, errs) {
+                                    match err.argument.and_then(|i| decorator_info.argument_span(i)) {
+                                        // Errors caused by an argument are reported at the argument
+                                        Some(span) => result.errors.push(
+                                            Diagnostic::error("invalid-argument", &err.message)
+                                                .spanning(&index, span),
+                                        ),
+                                        None => {
+                                            let span = err
+                                                .item
+                                                .and_then(|i| item_spans.get(i).copied())
+                                                .unwrap_or(value_span);
 
-                            if let DecoratorValidationResult::Error(errs) = result {
-                                for err in errs {
-                                    errors.push(err);
+                                            errors.push(key_error(
+                                                &index,
+                                                &current_key.0,
+                                                span,
+                                                &decorator_info,
+                                                err,
+                                            ))
+                                        }
+                                    }
                                 }
                             }
 
@@ -197,38 +600,105 @@ pub fn parse(content: &str) -> ParseResult {
                                 environment = Environment::Dev;
                             }
                         }
+                        // Cross key decorators are validated once all keys are parsed
+                        None if cross_key_decorators.contains_key(&decorator_info.key) => {
+                            constraints.push(decorator_info);
+                        }
                         None => {
                             let error_message =
                                 format!("Invalid decorator '{}'", decorator_info.key);
-                            result.errors.push(ParseError::new(error_message, pos));
+                            let mut diagnostic = Diagnostic::error("unknown-decorator", &error_message).at(
+                                &index,
+                                pos,
+                                decorator_info.key.chars().count() as u32 + 1,
+                            );
+
+                            let names: Vec<String> = decorators
+                                .keys()
+                                .chain(cross_key_decorators.keys())
+                                .cloned()
+                                .collect();
+
+                            if let Some(name) = closest_match(&decorator_info.key, &names) {
+                                let span = diagnostic.span;
+
+                                diagnostic = diagnostic.with_fix(
+                                    &format!("Did you mean '@{name}'?"),
+                                    span,
+                                    &format!("@{name}"),
+                                );
+                            }
+
+                            result.errors.push(diagnostic);
                         }
                     }
                 }
 
+                let key_span = index.span(current_key.1, current_key.0.chars().count() as u32);
+
+                if !naming::is_posix(&current_key.0) {
+                    let fixed = naming::to_posix(&current_key.0);
+                    let error_message = format!(
+                        "'{}' is not a valid key. Keys can only contain letters, digits and '_' and can't start with a digit.",
+                        current_key.0
+                    );
+
+                    errors.push(
+                        Diagnostic::error("invalid-key", &error_message)
+                            .spanning(&index, key_span)
+                            .with_fix(&format!("Rename to '{fixed}'"), key_span, &fixed)
+                            .for_key(&current_key.0),
+                    );
+                }
+
+                if let Some(deprecated) = constraints.iter().find(|d| d.key == "deprecated") {
+                    let error_message = match deprecated.arguments.get(0) {
+                        Some((_, decorators::DecoratorValue::String(reason))) => {
+                            format!("'{}' is deprecated. {reason}", current_key.0)
+                        }
+                        _ => format!("'{}' is deprecated.", current_key.0),
+                    };
+
+                    errors.push(
+                        Diagnostic::warning("deprecated", &error_message)
+                            .spanning(&index, key_span)
+                            .with_label(deprecated.span, "deprecated here")
+                            .for_key(&current_key.0),
+                    );
+                }
+
                 let key = Key {
                     key: current_key.0.to_owned(),
-                    valid: errors.len() == 0,
+                    valid: !errors.iter().any(|e| e.is_error()),
                     value: value_type,
                     position: current_key.1.to_owned(),
+                    value_span,
+                    item_spans,
                     scope,
                     decorators: constraints,
                     errors,
                     environment
                 };
 
-                if let Some(k) = keys.get(&key.key) {
-                    let error_message = format!("Duplicate key '{}' in the {} environment", k.key, k.environment.to_string());
-                    result
-                        .warnings
-                        .push(ParseError::new(error_message, current_key.1.to_owned()));
+                if let Some(k) = keys.get(&format!("{}-{}", key.key, key.environment)) {
+                    let error_message = format!("Duplicate key '{}' in the {} environment", k.key, k.environment);
+                    let key_length = k.key.chars().count() as u32;
+
+                    result.warnings.push(
+                        Diagnostic::warning("duplicate-key", &error_message)
+                            .at(&index, current_key.1.to_owned(), key_length)
+                            .with_label(index.span(k.position, key_length), "first defined here")
+                            .for_key(&k.key),
+                    );
                 }
 
-                keys.insert(format!("{}-{}", key.key.to_owned(), key.environment.to_string()), key);
+                keys.insert(format!("{}-{}", key.key.to_owned(), key.environment), key);
 
                 current_key = (String::new(), FilePosition::new());
                 current_decorators = Vec::new();
                 is_value = false;
                 is_array = false;
+                has_array = false;
                 is_string = false;
             }
 
@@ -242,33 +712,301 @@ pub fn parse(content: &str) -> ParseResult {
         } else {
             position.column += 1;
         }
+
+        i += 1;
     }
 
-    for (_, v) in keys {
-        if !v.valid {
-            result.valid = false;
-        }
+    // Decorators at the end of the file have no key to apply to
+    for (dec, pos) in current_decorators {
+        let name = dec.split('(').next().unwrap_or_default().trim();
+        let error_message = format!("'@{name}' is not followed by a key to apply to.");
 
-        result.keys.push(v);
+        result.errors.push(
+            Diagnostic::error("dangling-decorator", &error_message).at(
+                &index,
+                pos,
+                dec.chars().count() as u32 + 1,
+            ),
+        );
     }
 
-    if result.errors.len() > 0 {
-        result.valid = false;
+    for (_, v) in keys {
+        result.keys.push(v);
     }
 
     // Sorts the keys back to original order
     // since the hash map doesn't maintain the order
     result
         .keys
-        .sort_by(|a, b| a.position.line.cmp(&b.position.line));
+        .sort_by_key(|a| a.position.line);
+
+    // The previous name of a key can't be used by another key since both would be written by the build
+    for key in &result.keys {
+        for constraint in key.decorators.iter().filter(|d| d.key == "alias") {
+            if let Some((_, decorators::DecoratorValue::String(alias))) = constraint.arguments.get(0) {
+                if result.keys.iter().any(|k| k.key == *alias) {
+                    let error_message = format!(
+                        "'{alias}' can't be used as an alias of '{}' since it is also defined as a key.",
+                        key.key
+                    );
+
+                    result.errors.push(
+                        Diagnostic::error("alias-conflict", &error_message)
+                            .spanning(&index, constraint.arguments.list[0].span)
+                            .for_key(&key.key),
+                    );
+                }
+            }
+        }
+    }
+
+    // Validate the decorators that reference other keys now that all keys are known
+    //
+    // Keys are validated against the resolved keys of each environment they are part of so keys skipped by
+    // `@when` aren't compared against. The same error found in both environments is only reported once.
+    let environments = [Environment::Dev, Environment::Prod];
+
+    let active: Vec<Vec<bool>> = environments.iter().map(|e| result.active(e)).collect();
+    let resolved: Vec<Vec<Key>> = active.iter().map(|a| result.resolve_active(a)).collect();
+
+    let mut argument_errors: Vec<Diagnostic> = Vec::new();
+    let mut cross_key_errors: Vec<(usize, Diagnostic)> = Vec::new();
+
+    let is_same = |a: &Diagnostic, b: &Diagnostic| a.code == b.code && a.message == b.message && a.span == b.span;
+
+    for (i, key) in result.keys.iter().enumerate() {
+        let mut sets: Vec<&[Key]> = (0..environments.len())
+            .filter(|e| active[*e][i])
+            .map(|e| resolved[e].as_slice())
+            .collect();
+
+        // Keys that are skipped everywhere are still checked once so mistakes in their decorators are reported
+        if sets.is_empty() {
+            sets.push(&[]);
+        }
+
+        for constraint in &key.decorators {
+            let d = match cross_key_decorators.get(&constraint.key) {
+                Some(d) => d,
+                None => continue,
+            };
+
+            for set in &sets {
+                let keys = cross_key::KeySet {
+                    all: &result.keys,
+                    active: set,
+                };
+
+                let errs = match (d.validator)(key, &constraint.arguments, &keys) {
+                    DecoratorValidationResult::Error(errs) => errs,
+                    DecoratorValidationResult::Ok => continue,
+                };
+
+                for err in locate_items(&key.value, errs) {
+                    match err.argument.and_then(|i| constraint.argument_span(i)) {
+                        Some(span) => {
+                            let diagnostic = Diagnostic::error("invalid-argument", &err.message).spanning(&index, span);
+
+                            if !argument_errors.iter().any(|e| is_same(e, &diagnostic)) {
+                                argument_errors.push(diagnostic);
+                            }
+                        }
+                        None => {
+                            let span = err
+                                .item
+                                .and_then(|i| key.item_spans.get(i).copied())
+                                .unwrap_or(key.value_span);
+
+                            let diagnostic = key_error(&index, &key.key, span, constraint, err);
+
+                            if !cross_key_errors.iter().any(|(j, e)| *j == i && is_same(e, &diagnostic)) {
+                                cross_key_errors.push((i, diagnostic));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    result.errors.extend(argument_errors);
+
+    for (i, err) in cross_key_errors {
+        result.keys[i].errors.push(err);
+        result.keys[i].valid = false;
+    }
+
+    for (key, active) in result.keys.iter().zip(result.active_anywhere()) {
+        if active && !key.valid {
+            result.valid = false;
+        }
+    }
+
+    if !result.errors.is_empty() {
+        result.valid = false;
+    }
 
     result
 }
 
+/// Returns why the key can't be used as a key or `None` if it is valid
+fn invalid_key_name(key: &str) -> Option<String> {
+    let key = key.trim();
+
+    if key.is_empty() {
+        return Some(String::from("Expected a key before '='."));
+    }
+
+    if let Some(c) = key.chars().find(|c| matches!(c, '"' | '\'' | '[' | ']')) {
+        return Some(format!("'{key}' is not a valid key. Keys can't contain '{c}'."));
+    }
+
+    None
+}
+
+/// Returns the span between the offsets without the surrounding whitespace
+fn trimmed_span(index: &LineIndex, start: usize, end: usize) -> Span {
+    let text = index.text(Span::new(start, end));
+    let leading = text.len() - text.trim_start().len();
+
+    Span::new(start + leading, start + leading + text.trim().len())
+}
+
+/// Finds the span of each element of an array value in the source
+///
+/// Elements are separated by commas outside of strings the same way they are when the value is coerced.
+fn array_item_spans(index: &LineIndex, value_span: Span) -> Vec<Span> {
+    let text = index.text(value_span);
+
+    let open = match text.find('[') {
+        Some(open) => open + 1,
+        None => return Vec::new(),
+    };
+
+    let mut spans = Vec::new();
+    let mut is_string = false;
+    let mut start = open;
+    let mut end = text.len();
+
+    for (i, c) in text[open..].char_indices() {
+        let i = open + i;
+
+        match c {
+            '"' => is_string = !is_string,
+            ',' if !is_string => {
+                spans.push(trimmed_span(index, value_span.start + start, value_span.start + i));
+                start = i + 1;
+            }
+            ']' if !is_string => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    spans.push(trimmed_span(index, value_span.start + start, value_span.start + end));
+
+    spans.retain(|span| span.start < span.end);
+
+    spans
+}
+
+/// Marks which element of an array value caused each error
+///
+/// Validators report the elements they reject in order, so each error is matched to the
+/// next element with the same value. This places errors on repeated values (`["80", "80"]`)
+/// and values that are part of another (`80` in `8080`) on the right element.
+fn locate_items(value: &ValueType, errors: Vec<ValidationError>) -> Vec<ValidationError> {
+    let matches = |i: usize, err_value: &ValueType| match (value, err_value) {
+        (ValueType::StringArray(items), ValueType::String(v)) => items[i] == *v,
+        (ValueType::IntegerArray(items), ValueType::Integer(v)) => items[i] == *v,
+        (ValueType::IntegerArray(items), ValueType::Number(v)) => items[i] as f64 == *v,
+        (ValueType::NumberArray(items), ValueType::Number(v)) => items[i] == *v,
+        (ValueType::NumberArray(items), ValueType::Integer(v)) => items[i] == *v as f64,
+        _ => false,
+    };
+
+    let len = match value {
+        ValueType::StringArray(items) => items.len(),
+        ValueType::IntegerArray(items) => items.len(),
+        ValueType::NumberArray(items) => items.len(),
+        _ => return errors,
+    };
+
+    let mut next = 0;
+
+    errors
+        .into_iter()
+        .map(|err| {
+            if err.item.is_some() {
+                return err;
+            }
+
+            let item = match &err.value {
+                Some(err_value) => (next..len)
+                    .chain(0..next)
+                    .find(|i| matches(*i, err_value)),
+                None => None,
+            };
+
+            match item {
+                Some(i) => {
+                    next = i + 1;
+                    err.at_item(i)
+                }
+                None => err,
+            }
+        })
+        .collect()
+}
+
+/// Creates the diagnostic for an error reported by a decorator on a key
+///
+/// The error is placed on the span of the value (or element) that caused it and labels the decorator that reported it.
+fn key_error(
+    index: &LineIndex,
+    key: &str,
+    span: Span,
+    decorator: &DecoratorParseResult,
+    err: ValidationError,
+) -> Diagnostic {
+    Diagnostic::error(&decorator.key, &err.message)
+        .spanning(index, span)
+        .with_label(decorator.span, "required by this decorator")
+        .for_key(key)
+}
+
+/// Coerces the contents of an array (without the brackets) into an array value type
+///
+/// # Examples
+/// ```
+/// use vnv::parsing::{coerce_array_value_type, ValueType};
+///
+/// let value = coerce_array_value_type("\"https://github.com\"");
+///
+/// assert!(matches!(value, ValueType::StringArray(v) if v.len() == 1));
+/// ```
+pub fn coerce_array_value_type(val: &str) -> ValueType {
+    if val.is_empty() {
+        return ValueType::StringArray(Vec::new());
+    }
+
+    match coerce_value_type(val) {
+        ValueType::Integer(v) => ValueType::IntegerArray(vec![v]),
+        ValueType::Number(v) => ValueType::NumberArray(vec![v]),
+        ValueType::String(v) => ValueType::StringArray(vec![v]),
+        array => array,
+    }
+}
+
 /// Coerces the string value into a value type
 pub fn coerce_value_type(val: &str) -> ValueType {
     // get value type
-    if val.parse::<f64>().is_ok() {
+    if let Ok(v) = val.parse::<i64>() {
+        // integer
+        return ValueType::Integer(v);
+    } else if val.parse::<f64>().is_ok() {
         // number
         // Should be able to unwrap here because of is_ok
         return ValueType::Number(val.parse::<f64>().unwrap());
@@ -284,22 +1022,29 @@ pub fn coerce_value_type(val: &str) -> ValueType {
         let mut current = String::new();
 
         for (i, c) in chars.into_iter().enumerate() {
-            if c == '"' && !(i == len - 1) {
+            if c == '"' && (i != len - 1) {
                 has_string = true;
                 is_string = !is_string;
             } else if (c == ',' && !is_string) || i == len - 1 {
                 if i == len - 1 && !is_string {
-                    current.push_str(&c.to_string());
+                    current.push(c);
                 }
                 values.push(current);
                 current = String::new();
-            } else if (c == ' ' && is_string) || c != ' ' {
-                current.push_str(&c.to_string());
+            } else if is_string || c != ' ' {
+                current.push(c);
             }
         }
 
         if values.len() > 1 {
             if !has_string {
+                // Arrays of whole numbers stay integers so large values don't lose precision
+                let integers: Result<Vec<i64>, _> = values.iter().map(|v| v.parse::<i64>()).collect();
+
+                if let Ok(integers) = integers {
+                    return ValueType::IntegerArray(integers);
+                }
+
                 // coerce to number array
                 let mut are_numbers = true;
 
@@ -0,0 +1,209 @@
+use crate::diagnostics::{Diagnostic, LineIndex};
+use crate::parsing::{config::Naming, ParseResult, Scope};
+
+/// Returns true if the key follows the POSIX rules for environment variable names
+///
+/// Names can only contain letters, digits and `_` and can't start with a digit.
+///
+/// # Examples
+/// ```
+/// use vnv::parsing::naming::is_posix;
+///
+/// assert!(is_posix("API_URL"));
+/// assert!(!is_posix("1API_URL"));
+/// assert!(!is_posix("API URL"));
+/// ```
+pub fn is_posix(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Converts the key into a valid POSIX name by replacing invalid characters with `_`
+///
+/// # Examples
+/// ```
+/// use vnv::parsing::naming::to_posix;
+///
+/// assert_eq!(to_posix("api-url"), "api_url");
+/// assert_eq!(to_posix("1API"), "_1API");
+/// ```
+pub fn to_posix(key: &str) -> String {
+    let mut name: String = key
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name.insert(0, '_');
+    }
+
+    name
+}
+
+/// Returns true if the key is written in SCREAMING_SNAKE_CASE
+///
+/// A single leading `_` is allowed.
+///
+/// # Examples
+/// ```
+/// use vnv::parsing::naming::is_screaming_snake_case;
+///
+/// assert!(is_screaming_snake_case("API_URL"));
+/// assert!(!is_screaming_snake_case("apiUrl"));
+/// assert!(!is_screaming_snake_case("API__URL"));
+/// ```
+pub fn is_screaming_snake_case(key: &str) -> bool {
+    if key.starts_with(|c: char| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let key = key.strip_prefix('_').unwrap_or(key);
+
+    key.split('_').all(|word| {
+        !word.is_empty() && word.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    })
+}
+
+/// Converts the key to SCREAMING_SNAKE_CASE
+///
+/// Words are split at characters that aren't letters or digits and where the case changes.
+///
+/// # Examples
+/// ```
+/// use vnv::parsing::naming::to_screaming_snake_case;
+///
+/// assert_eq!(to_screaming_snake_case("apiUrl"), "API_URL");
+/// assert_eq!(to_screaming_snake_case("HTTPServer"), "HTTP_SERVER");
+/// assert_eq!(to_screaming_snake_case("api-url"), "API_URL");
+/// ```
+pub fn to_screaming_snake_case(key: &str) -> String {
+    let chars: Vec<char> = key.trim().chars().collect();
+
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+
+    for (i, c) in chars.iter().copied().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !word.is_empty() {
+                words.push(word);
+                word = String::new();
+            }
+            continue;
+        }
+
+        let previous = if i > 0 { chars.get(i - 1) } else { None };
+        let next = chars.get(i + 1);
+
+        let starts_word = c.is_ascii_uppercase()
+            && match previous {
+                Some(p) if p.is_ascii_lowercase() => true,
+                // The last capital of an acronym starts the next word (`HTTPServer`)
+                Some(p) if p.is_ascii_uppercase() => next.is_some_and(|n| n.is_ascii_lowercase()),
+                _ => false,
+            };
+
+        if starts_word && !word.is_empty() {
+            words.push(word);
+            word = String::new();
+        }
+
+        word.push(c.to_ascii_uppercase());
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    to_posix(&words.join("_"))
+}
+
+/// Suggests a name for the key that follows the naming policy
+fn suggest(key: &str, naming: &Naming, scope: &Scope) -> String {
+    let mut name = if naming.screaming_snake_case {
+        to_screaming_snake_case(key)
+    } else {
+        to_posix(key)
+    };
+
+    let prefix = naming.prefixes.get(scope);
+
+    if let Some(prefix) = prefix {
+        if !name.starts_with(prefix) {
+            name = format!("{prefix}{name}");
+        }
+    }
+
+    if naming.forbids(&name) {
+        name = format!("{}{name}", prefix.unwrap_or("APP_"));
+    }
+
+    name
+}
+
+/// Validates the keys against the naming policy from the config
+///
+/// Violations are added to the errors of each key with a fix that renames the key to follow the whole policy.
+pub fn apply(result: &mut ParseResult, content: &str, naming: &Naming) {
+    let index = LineIndex::new(content);
+
+    let mut valid = true;
+
+    let active = result.active_anywhere();
+
+    for (key, active) in result.keys.iter_mut().zip(active) {
+        let key_span = index.span(key.position, key.key.chars().count() as u32);
+        let suggestion = suggest(&key.key, naming, &key.scope);
+
+        let mut violations: Vec<(&str, String)> = Vec::new();
+
+        // Keys that aren't valid POSIX names are already reported by the parser
+        if naming.screaming_snake_case && is_posix(&key.key) && !is_screaming_snake_case(&key.key) {
+            violations.push((
+                "key-case",
+                format!("'{}' should be written in SCREAMING_SNAKE_CASE.", key.key),
+            ));
+        }
+
+        if let Some(prefix) = naming.prefixes.get(&key.scope) {
+            if !key.key.starts_with(prefix) {
+                let scope = match key.scope {
+                    Scope::Public => "public",
+                    Scope::Private => "private",
+                };
+
+                violations.push((
+                    "key-prefix",
+                    format!("'{}' should start with '{prefix}' since it is {scope}.", key.key),
+                ));
+            }
+        }
+
+        if naming.forbids(&key.key) {
+            violations.push(("forbidden-key", format!("'{}' is not allowed as a key.", key.key)));
+        }
+
+        for (code, message) in violations {
+            key.errors.push(
+                Diagnostic::error(code, &message)
+                    .spanning(&index, key_span)
+                    .with_fix(&format!("Rename to '{suggestion}'"), key_span, &suggestion)
+                    .for_key(&key.key),
+            );
+            key.valid = false;
+
+            // Errors of keys skipped in every environment are hidden so they don't make the result invalid
+            valid = valid && !active;
+        }
+    }
+
+    if !valid {
+        result.valid = false;
+    }
+}
@@ -0,0 +1,138 @@
+use crate::parsing::ValueType;
+use std::fmt;
+
+/// A kind of value that is written with a unit like `30s`, `10MB` or `5%`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    /// Normalized to milliseconds
+    Duration,
+    /// Normalized to bytes
+    Bytes,
+    /// Normalized to a ratio where `100%` is `1`
+    Percent,
+}
+
+const DURATION_UNITS: [(&str, f64); 5] = [
+    ("ms", 1.0),
+    ("s", 1000.0),
+    ("m", 60_000.0),
+    ("h", 3_600_000.0),
+    ("d", 86_400_000.0),
+];
+
+const BYTE_UNITS: [(&str, f64); 9] = [
+    ("b", 1.0),
+    ("kb", 1e3),
+    ("mb", 1e6),
+    ("gb", 1e9),
+    ("tb", 1e12),
+    ("kib", 1024.0),
+    ("mib", 1_048_576.0),
+    ("gib", 1_073_741_824.0),
+    ("tib", 1_099_511_627_776.0),
+];
+
+impl Unit {
+    /// Returns the unit validated by the decorator with the provided name
+    pub fn from_decorator(name: &str) -> Option<Unit> {
+        match name {
+            "duration" => Some(Unit::Duration),
+            "bytes" => Some(Unit::Bytes),
+            "percent" => Some(Unit::Percent),
+            _ => None,
+        }
+    }
+
+    /// Returns an example value used in error messages
+    pub fn example(&self) -> &'static str {
+        match self {
+            Unit::Duration => "30s",
+            Unit::Bytes => "10MB",
+            Unit::Percent => "5%",
+        }
+    }
+
+    fn units(&self) -> &'static [(&'static str, f64)] {
+        match self {
+            Unit::Duration => &DURATION_UNITS,
+            Unit::Bytes => &BYTE_UNITS,
+            Unit::Percent => &[("%", 0.01)],
+        }
+    }
+
+    /// Parses the value and converts it to the canonical unit
+    ///
+    /// # Examples
+    /// ```
+    /// use vnv::parsing::units::Unit;
+    ///
+    /// assert_eq!(Ok(90_000.0), Unit::Duration.parse("1.5m"));
+    /// assert_eq!(Ok(1024.0), Unit::Bytes.parse("1KiB"));
+    /// assert_eq!(Ok(0.05), Unit::Percent.parse("5%"));
+    /// ```
+    pub fn parse(&self, value: &str) -> Result<f64, String> {
+        let value = value.trim();
+
+        let split = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+            .unwrap_or(value.len());
+
+        let (number, suffix) = value.split_at(split);
+        let suffix = suffix.trim().to_lowercase();
+
+        let number = number.parse::<f64>();
+        let multiplier = self
+            .units()
+            .iter()
+            .find(|(unit, _)| *unit == suffix)
+            .map(|(_, multiplier)| *multiplier);
+
+        match (number, multiplier) {
+            (Ok(number), Some(multiplier)) if number >= 0.0 => Ok(number * multiplier),
+            _ => Err(format!(
+                "'{value}' is not a valid {self}. Ex: `{}`",
+                self.example()
+            )),
+        }
+    }
+
+    /// Converts a string or string array value to the canonical unit
+    ///
+    /// Returns `None` if the value can't be parsed.
+    pub fn normalize(&self, value: &ValueType) -> Option<ValueType> {
+        match value {
+            ValueType::String(v) => {
+                let normalized = self.parse(v).ok()?;
+
+                if normalized.fract() == 0.0 && normalized.abs() < i64::MAX as f64 {
+                    return Some(ValueType::Integer(normalized as i64));
+                }
+
+                return Some(ValueType::Number(normalized));
+            }
+            ValueType::StringArray(values) => {
+                let normalized = values
+                    .iter()
+                    .map(|v| self.parse(v).ok())
+                    .collect::<Option<Vec<f64>>>()?;
+
+                if normalized.iter().all(|n| n.fract() == 0.0 && n.abs() < i64::MAX as f64) {
+                    return Some(ValueType::IntegerArray(normalized.iter().map(|n| *n as i64).collect()));
+                }
+
+                return Some(ValueType::NumberArray(normalized));
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unit::Duration => write!(f, "duration"),
+            Unit::Bytes => write!(f, "byte size"),
+            Unit::Percent => write!(f, "percentage"),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use std::{fs, path::PathBuf};
+
+/// Creates an empty directory for the test in the temp directory
+///
+/// The group keeps tests of different modules that use the same name apart.
+pub fn temp_dir(group: &str, name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vnv-{group}-{name}-{}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
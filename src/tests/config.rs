@@ -0,0 +1,299 @@
+use crate::parsing::config::{self, with_suggestion, Cloak, Origin, Target, TargetFormat};
+use crate::parsing::{parse, Environment, Scope};
+use crate::tests::common::temp_dir;
+use std::fs;
+
+#[test]
+fn reads_json() {
+    let dir = temp_dir("config", "json");
+    let path = dir.join(config::JSON_FILE);
+
+    fs::write(&path, r#"{"src": "app.vnv", "build": {"minify": true}}"#).unwrap();
+
+    let options = config::parse(&path).unwrap();
+
+    assert_eq!(dir.join("app.vnv").to_string_lossy(), options.src);
+    assert!(options.build.minify);
+    assert_eq!(dir.join(".env").to_string_lossy(), options.build.output);
+}
+
+#[test]
+fn unknown_fields_fail() {
+    let dir = temp_dir("config", "unknown");
+    let path = dir.join(config::JSON_FILE);
+
+    fs::write(&path, r#"{"build": {"minfy": true}}"#).unwrap();
+
+    let err = config::parse(&path).unwrap_err();
+
+    assert!(err.message.starts_with("unknown field `minfy`"));
+    assert!(err.message.ends_with("Did you mean 'minify'?"));
+}
+
+#[test]
+fn invalid_types_fail() {
+    let dir = temp_dir("config", "types");
+    let path = dir.join(config::JSON_FILE);
+
+    fs::write(&path, r#"{"build": {"minify": "yes"}}"#).unwrap();
+
+    let err = config::parse(&path).unwrap_err();
+
+    assert_eq!(
+        "invalid type: string \"yes\", expected a boolean at line 1 column 26",
+        err.message
+    );
+}
+
+#[test]
+fn reads_cloak() {
+    let dir = temp_dir("config", "cloak");
+    let path = dir.join(config::JSON_FILE);
+
+    for (json, expected) in [
+        ("true", Cloak::All),
+        ("false", Cloak::Off),
+        ("\"private\"", Cloak::Private),
+    ] {
+        fs::write(&path, format!("{{\"cloak\": {json}, \"cloak_reveal\": 2}}")).unwrap();
+
+        let options = config::parse(&path).unwrap();
+
+        assert_eq!(expected, options.cloak);
+        assert_eq!(2, options.cloak_reveal);
+    }
+
+    fs::write(&path, r#"{"cloak": "public"}"#).unwrap();
+
+    let err = config::parse(&path).unwrap_err();
+
+    assert!(err.message.starts_with("invalid value: string \"public\", expected a boolean or \"private\""));
+
+    let mut options = config::Options::new();
+
+    options.set("cloak", "yes").unwrap();
+    assert_eq!(Cloak::All, options.cloak);
+
+    options.set("cloak", "private").unwrap();
+    assert_eq!(Cloak::Private, options.cloak);
+}
+
+#[test]
+fn reads_toml() {
+    let dir = temp_dir("config", "toml");
+    let path = dir.join(config::TOML_FILE);
+
+    fs::write(&path, "cloak = true\n\n[naming]\nforbidden = [\"PATH\"]\n").unwrap();
+
+    let options = config::parse(&path).unwrap();
+
+    assert_eq!(Cloak::All, options.cloak);
+    assert_eq!(vec!["PATH"], options.naming.forbidden);
+}
+
+#[test]
+fn reads_cargo_metadata() {
+    let dir = temp_dir("config", "cargo");
+    let path = dir.join(config::CARGO_FILE);
+
+    fs::write(
+        &path,
+        "[package]\nname = \"app\"\n\n[package.metadata.vnv.build]\noutput = \".env.local\"\n",
+    )
+    .unwrap();
+
+    let options = config::parse(&path).unwrap();
+
+    assert_eq!(dir.join(".env.local").to_string_lossy(), options.build.output);
+}
+
+#[test]
+fn finds_config_in_parents() {
+    let dir = temp_dir("config", "find");
+    let nested = dir.join("a").join("b");
+
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(dir.join(config::TOML_FILE), "").unwrap();
+    // A manifest without the table is skipped
+    fs::write(dir.join("a").join(config::CARGO_FILE), "[package]\nname = \"app\"\n").unwrap();
+
+    let found = config::find(&nested).unwrap();
+
+    assert_eq!(
+        dir.join(config::TOML_FILE).canonicalize().unwrap(),
+        found.canonicalize().unwrap()
+    );
+}
+
+#[test]
+fn suggests_fields() {
+    let message = with_suggestion("unknown field `clok`, expected one of `src`, `cloak`, `build`, `naming`");
+
+    assert!(message.ends_with("Did you mean 'cloak'?"));
+
+    let message = with_suggestion("unknown field `zzzzzz`, expected one of `src`, `cloak`");
+
+    assert_eq!("unknown field `zzzzzz`, expected one of `src`, `cloak`", message);
+}
+
+#[test]
+fn overrides_take_precedence() {
+    let dir = temp_dir("config", "overrides");
+    let path = dir.join(config::JSON_FILE);
+
+    fs::write(&path, r#"{"src": "app.vnv", "cloak": true, "build": {"output": ".env.file"}}"#).unwrap();
+
+    let env = |name: &str| match name {
+        "VNV_BUILD_OUTPUT" => Some(String::from(".env.env")),
+        "VNV_BUILD_MINIFY" => Some(String::from("true")),
+        "VNV_CLOAK" => Some(String::from("false")),
+        _ => None,
+    };
+
+    let resolved = config::resolve(
+        Some(&path),
+        env,
+        &[("build.output", String::from(".env.flag"))],
+    )
+    .unwrap();
+
+    assert_eq!(".env.flag", resolved.options.build.output);
+    assert!(resolved.options.build.minify);
+    assert_eq!(Cloak::Off, resolved.options.cloak);
+    assert_eq!(dir.join("app.vnv").to_string_lossy(), resolved.options.src);
+
+    assert_eq!(
+        Some(&Origin::Flag(String::from("--build-output"))),
+        resolved.origin("build.output")
+    );
+    assert_eq!(
+        Some(&Origin::Env(String::from("VNV_BUILD_MINIFY"))),
+        resolved.origin("build.minify")
+    );
+    assert_eq!(Some(&Origin::File(path)), resolved.origin("src"));
+    assert_eq!(Some(&Origin::Default), resolved.origin("build.normalize"));
+}
+
+#[test]
+fn overrides_lists_and_prefixes() {
+    let mut options = config::Options::new();
+
+    options.set("naming.forbidden", "PATH, HOME,").unwrap();
+    options.set("naming.prefixes.public", "PUBLIC_").unwrap();
+
+    assert_eq!(vec!["PATH", "HOME"], options.naming.forbidden);
+    assert_eq!(Some(String::from("PUBLIC_")), options.naming.prefixes.public);
+
+    options.set("naming.prefixes.public", "").unwrap();
+
+    assert_eq!(None, options.naming.prefixes.public);
+}
+
+#[test]
+fn invalid_overrides_fail() {
+    let env = |name: &str| match name {
+        "VNV_BUILD_MINIFY" => Some(String::from("maybe")),
+        _ => None,
+    };
+
+    let dir = temp_dir("config", "invalid-override");
+    let path = dir.join(config::TOML_FILE);
+
+    fs::write(&path, "").unwrap();
+
+    let err = config::resolve(Some(&path), env, &[]).unwrap_err();
+
+    assert_eq!(Origin::Env(String::from("VNV_BUILD_MINIFY")), err.origin);
+    assert_eq!(
+        "Invalid value for VNV_BUILD_MINIFY: 'maybe' is not a valid boolean. Expected 'true' or 'false'.",
+        err.to_string()
+    );
+}
+
+#[test]
+fn reads_targets() {
+    let dir = temp_dir("config", "targets");
+    let path = dir.join(config::TOML_FILE);
+
+    fs::write(
+        &path,
+        r#"
+[build]
+minify = true
+
+[[build.targets]]
+name = "backend"
+output = ".env"
+minify = false
+
+[[build.targets]]
+name = "sidecar"
+output = "sidecar.json"
+format = "json"
+environment = "production"
+scope = "public"
+include = ["DB_*"]
+"#,
+    )
+    .unwrap();
+
+    let targets = config::parse(&path).unwrap().build.targets();
+
+    assert_eq!(2, targets.len());
+    assert_eq!(Some(false), targets[0].minify);
+    assert_eq!(TargetFormat::Dotenv, targets[0].format);
+
+    assert_eq!(dir.join("sidecar.json").to_string_lossy(), targets[1].output);
+    assert_eq!(TargetFormat::Json, targets[1].format);
+    assert_eq!(Some(Environment::Prod), targets[1].environment);
+    assert_eq!(Some(Scope::Public), targets[1].scope);
+    assert_eq!(Some(true), targets[1].minify);
+}
+
+#[test]
+fn uses_output_without_targets() {
+    let options = config::Options::new();
+
+    let targets = options.build.targets();
+
+    assert_eq!(1, targets.len());
+    assert_eq!(config::DEFAULT_TARGET, targets[0].name);
+    assert_eq!(".env", targets[0].output);
+}
+
+#[test]
+fn targets_filter_keys() {
+    let result = parse("@public\nPUBLIC_URL=\"x\"\nDB_HOST=\"db\"\nDB_PASSWORD=\"secret\"\nPORT=80");
+
+    let target = Target {
+        scope: Some(Scope::Private),
+        include: vec![String::from("DB_*"), String::from("PORT")],
+        exclude: vec![String::from("*_PASSWORD")],
+        ..Target::default()
+    };
+
+    let keys: Vec<String> = result
+        .keys
+        .iter()
+        .filter(|k| target.includes(k))
+        .map(|k| k.key.to_owned())
+        .collect();
+
+    assert_eq!(vec!["DB_HOST", "PORT"], keys);
+}
+
+#[test]
+fn duplicate_targets_fail() {
+    let dir = temp_dir("config", "duplicate-targets");
+    let path = dir.join(config::JSON_FILE);
+
+    fs::write(
+        &path,
+        r#"{"build": {"targets": [{"name": "web", "output": ".env"}, {"name": "web", "output": ".env.web"}]}}"#,
+    )
+    .unwrap();
+
+    let err = config::parse(&path).unwrap_err();
+
+    assert_eq!("There are multiple build targets named 'web'.", err.message);
+}
@@ -0,0 +1,85 @@
+use crate::de::Error;
+use crate::{from_str, Env};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+struct AppConfig {
+    port: u16,
+    sample_rate: f32,
+    hosts: Vec<String>,
+    retry_delays: Vec<u64>,
+    log_level: LogLevel,
+    debug: bool,
+    api_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum LogLevel {
+    Info,
+    Debug,
+}
+
+#[test]
+fn deserializes_struct() {
+    let content = "PORT=3000\nSAMPLE_RATE=0.5\nHOSTS=[\"a.com\", \"b.com\"]\nRETRY_DELAYS=[100, 200]\n@dev\nLOG_LEVEL=\"debug\"\n@prod\nLOG_LEVEL=\"info\"\nDEBUG=\"false\"";
+
+    let cfg: AppConfig = match from_str(content, Env::Prod) {
+        Ok(cfg) => cfg,
+        Err(err) => panic!("Result should have been valid. {err}"),
+    };
+
+    assert_eq!(3000, cfg.port);
+    assert_eq!(0.5, cfg.sample_rate);
+    assert_eq!(vec!["a.com", "b.com"], cfg.hosts);
+    assert_eq!(vec![100, 200], cfg.retry_delays);
+    assert_eq!(LogLevel::Info, cfg.log_level);
+    assert!(!cfg.debug);
+    assert_eq!(None, cfg.api_url);
+}
+
+#[test]
+fn deserializes_map() {
+    let content = "PORT=3000\nHOST=\"localhost\"";
+
+    let map: HashMap<String, String> = match from_str(content, Env::Dev) {
+        Ok(map) => map,
+        Err(err) => panic!("Result should have been valid. {err}"),
+    };
+
+    assert_eq!("localhost", map["HOST"]);
+}
+
+#[test]
+fn validation_errors_have_positions() {
+    let content = "@min(1024)\nPORT=80";
+
+    match from_str::<HashMap<String, u16>>(content, Env::Prod) {
+        Err(Error::Invalid(diagnostics)) => {
+            assert_eq!(1, diagnostics.len());
+            assert_eq!("80 is too small. Minimum value is 1024.", diagnostics[0].message);
+            assert_eq!(2, diagnostics[0].position.line);
+        }
+        _ => panic!("Result should have been invalid."),
+    }
+}
+
+#[test]
+fn type_errors_have_positions() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        port: u16,
+    }
+
+    let content = "HOST=\"localhost\"\nPORT=70000";
+
+    match from_str::<Config>(content, Env::Prod) {
+        Err(Error::Custom { key, position, .. }) => {
+            assert_eq!(Some("PORT".to_owned()), key);
+            assert_eq!(2, position.unwrap().line);
+        }
+        _ => panic!("Result should have been invalid."),
+    }
+}
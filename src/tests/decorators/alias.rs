@@ -0,0 +1,63 @@
+use crate::parsing::parse;
+
+#[test]
+fn records_previous_names() {
+    let content = "@alias(\"DB_URL\")\n@alias(\"POSTGRES_URL\")\nDATABASE_URL=\"postgres://localhost\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+
+    assert_eq!(vec!["DB_URL", "POSTGRES_URL"], result.keys[0].aliases());
+}
+
+#[test]
+fn invalid_name_fails() {
+    let expected_error_message = "'DB URL' is not a valid key to use as an alias.";
+
+    let content = "@alias(\"DB URL\")\nDATABASE_URL=\"postgres://localhost\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(expected_error_message, result.errors[0].message);
+}
+
+#[test]
+fn alias_of_existing_key_fails() {
+    let expected_error_message =
+        "'DB_URL' can't be used as an alias of 'DATABASE_URL' since it is also defined as a key.";
+
+    let content = "DB_URL=\"a\"\n@alias(\"DB_URL\")\nDATABASE_URL=\"b\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!("alias-conflict", result.errors[0].code);
+    assert_eq!(expected_error_message, result.errors[0].message);
+}
+
+#[test]
+fn deprecated_keys_warn() {
+    let content = "@deprecated(\"Use DATABASE_URL instead\")\nDB_URL=\"postgres://localhost\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+
+    let warning = &result.keys[0].errors[0];
+
+    assert!(!warning.is_error());
+    assert_eq!("deprecated", warning.code);
+    assert_eq!("'DB_URL' is deprecated. Use DATABASE_URL instead", warning.message);
+}
@@ -0,0 +1,127 @@
+use crate::decorators::{self, DecoratorValue};
+use crate::parsing::parse;
+
+#[test]
+fn positional_and_named_arguments_parsed() {
+    let result = decorators::parse("between(1, 10, inclusive=true)").unwrap();
+
+    assert_eq!("between", result.key);
+    assert_eq!(3, result.arguments.len());
+
+    match result.arguments.get(1) {
+        Some((1, DecoratorValue::Integer(v))) => assert_eq!(10, *v),
+        _ => panic!("Expected the second positional argument to be the number 10"),
+    }
+
+    match result.arguments.named("inclusive") {
+        Some((2, DecoratorValue::Bool(v))) => assert!(*v),
+        _ => panic!("Expected 'inclusive' to be the bool true"),
+    }
+}
+
+#[test]
+fn list_argument_parsed() {
+    let result = decorators::parse("oneOf([\"a\", \"b, c\"], 3)").unwrap();
+
+    match result.arguments.get(0) {
+        Some((_, DecoratorValue::List(items))) => {
+            assert_eq!(2, items.len());
+
+            match &items[1] {
+                DecoratorValue::String(v) => assert_eq!("b, c", v),
+                _ => panic!("Expected a string"),
+            }
+        }
+        _ => panic!("Expected a list"),
+    }
+}
+
+#[test]
+fn unterminated_string_fails() {
+    let result = decorators::parse("matches(\"^abc)");
+
+    match result {
+        Ok(_) => panic!("Result should have been a syntax error."),
+        Err(err) => {
+            assert_eq!("Unterminated string. Expected a closing '\"'.", err.message);
+            // Offset of the opening quote after `matches(`
+            assert_eq!(8, err.offset);
+        }
+    }
+}
+
+#[test]
+fn positional_after_named_fails() {
+    let result = decorators::parse("between(min=1, 10)");
+
+    match result {
+        Ok(_) => panic!("Result should have been a syntax error."),
+        Err(err) => assert_eq!(
+            "Positional arguments must come before named arguments.",
+            err.message
+        ),
+    }
+}
+
+#[test]
+fn invalid_argument_reported_at_argument() {
+    let expected_error_message =
+        "'abc' is not valid for decorator type 'min'. 'min' requires a number value.";
+
+    let content = "@min(\"abc\")\nSOMETHING=10";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(1, result.errors.len());
+
+    let err = &result.errors[0];
+
+    assert_eq!(expected_error_message, err.message);
+    assert_eq!(1, err.position.line);
+    assert_eq!(6, err.position.column);
+    assert_eq!(5, err.length);
+}
+
+#[test]
+fn unexpected_named_argument_fails() {
+    let expected_error_message = "The max decorator does not accept the named argument 'flags'.";
+
+    let content = "@max(10, flags=\"i\")\nSOMETHING=10";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for err in result.errors {
+        assert_eq!(expected_error_message, err.message);
+        assert_eq!(10, err.position.column);
+    }
+}
+
+#[test]
+fn each_false_only_accepted_where_it_has_meaning() {
+    let expected_error_message =
+        "The startsWith decorator can only validate the items of an array. 'each' can't be false.";
+
+    let content = "@startsWith(\"sk_\", each=false)\nKEYS=[\"sk_a\", \"sk_b\"]";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(1, result.errors.len());
+
+    let err = &result.errors[0];
+
+    assert_eq!(expected_error_message, err.message);
+    assert_eq!(20, err.position.column);
+    assert_eq!(10, err.length);
+}
@@ -0,0 +1,50 @@
+use crate::parsing::parse;
+
+#[test]
+fn within_bounds_passes() {
+    let content = "@bytes(max=\"1GiB\")\nMAX_UPLOAD=10MB";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn too_large_fails() {
+    let expected_error_message = "'2GB' is too large. Maximum value is 1GiB.";
+
+    let content = "@bytes(max=\"1GiB\")\nMAX_UPLOAD=2GB";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn invalid_value_fails() {
+    let expected_error_message = "'lots' is not a valid byte size. Ex: `10MB`";
+
+    let content = "@bytes\nMAX_UPLOAD=lots";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::parsing::parse;
+
+#[test]
+fn documents_any_type() {
+    let content = "@description(\"Ports of the services\")\n@example(\"[8080, 8081]\")\n@owner(\"platform\")\n@deprecated(\"Use SERVICE_PORTS instead\")\nPORTS=[8080, 8081]";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+
+    let key = &result.keys[0];
+
+    assert_eq!(Some(String::from("Ports of the services")), key.annotation("description"));
+    assert_eq!(Some(String::from("[8080, 8081]")), key.annotation("example"));
+    assert_eq!(Some(String::from("platform")), key.annotation("owner"));
+    assert_eq!(Some(String::from("Use SERVICE_PORTS instead")), key.annotation("deprecated"));
+}
+
+#[test]
+fn missing_value_fails() {
+    let expected_error_message = "The description decorator requires a value to be provided with it. Ex: `@description(\"The port the server listens on\")`";
+
+    let content = "@description\nPORT=3000";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn non_string_value_fails() {
+    let expected_error_message = "'5' is not valid for decorator type 'owner'. 'owner' requires a string value.";
+
+    let content = "@owner(5)\nPORT=3000";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(expected_error_message, result.errors[0].message);
+}
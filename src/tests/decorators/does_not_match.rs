@@ -137,4 +137,22 @@ fn string_array_passes() {
             }
         }
     }
-}
\ No newline at end of file
+}
+#[test]
+fn case_insensitive_flag_fails() {
+    let expected_error_message = "'ADMIN' matches '^admin$'.";
+
+    let content = "@doesNotMatch(\"^admin$\", flags=\"i\")\nUSERNAME=\"ADMIN\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::parsing::parse;
+
+#[test]
+fn within_bounds_passes() {
+    let content = "@duration(min=\"1s\", max=\"5m\")\nTIMEOUT=30s";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn too_large_fails() {
+    let expected_error_message = "'10m' is too large. Maximum value is 5m.";
+
+    let content = "@duration(min=\"1s\", max=\"5m\")\nTIMEOUT=10m";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        match key.value {
+            crate::parsing::ValueType::String(v) => assert_eq!("10m", v),
+            _ => panic!("Invalid value type. Expected String"),
+        }
+
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn too_small_fails() {
+    let expected_error_message = "'500ms' is too small. Minimum value is 1s.";
+
+    let content = "@duration(min=\"1s\")\nRETRY_DELAYS=[\"2s\", \"500ms\"]";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        assert_eq!(1, key.errors.len());
+
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn missing_unit_fails() {
+    let expected_error_message = "30 is missing a unit. duration values must be written with a unit. Ex: `30s`";
+
+    let content = "@duration\nTIMEOUT=30";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn invalid_bound_fails() {
+    let expected_error_message = "'5 minutes' is not a valid duration. Ex: `30s`";
+
+    let content = "@duration(max=\"5 minutes\")\nTIMEOUT=30s";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(1, result.errors.len());
+    assert_eq!(expected_error_message, result.errors[0].message);
+    assert_eq!(11, result.errors[0].position.column);
+}
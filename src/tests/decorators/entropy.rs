@@ -0,0 +1,49 @@
+use crate::parsing::parse;
+
+#[test]
+fn weak_secret_fails() {
+    let expected_error_message =
+        "Value is too weak. It has 22.0 bits of entropy but at least 64 are required.";
+
+    let content = "@entropy(min=64)\nAPI_KEY=\"changeme\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn strong_secret_passes() {
+    let content = "@entropy(min=64)\nAPI_KEY=\"7fG9qL2xVb8KpZ3wR1tY\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn missing_min_fails() {
+    let expected_error_message =
+        "The entropy decorator only accepts the named argument 'min'. Ex: `@entropy(min=64)`";
+
+    let content = "@entropy(64)\nAPI_KEY=\"7fG9qL2xVb8KpZ3wR1tY\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(1, result.errors.len());
+    assert_eq!(expected_error_message, result.errors[0].message);
+}
@@ -0,0 +1,70 @@
+use crate::parsing::parse;
+
+#[test]
+fn number_fails() {
+    let expected_key = "MAX_POOL";
+    let expected_value = 5.0;
+    let min_pool = 10.0;
+
+    let expected_error_message =
+        format!("{expected_value} must be greater than or equal to MIN_POOL ({min_pool}).");
+
+    let content = format!("MIN_POOL={min_pool}\n@gte(\"MIN_POOL\")\n{expected_key}={expected_value}");
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        if key.key == expected_key {
+            assert_eq!(1, key.errors.len());
+
+            for err in key.errors {
+                assert_eq!(expected_error_message, err.message);
+            }
+        }
+    }
+}
+
+#[test]
+fn number_passes() {
+    let content = "MIN_POOL=10\n@gte(\"MIN_POOL\")\nMAX_POOL=10";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn compares_within_environment() {
+    let content = "@dev\nMIN_POOL=1\n@prod\nMIN_POOL=20\n@dev\n@gte(\"MIN_POOL\")\nMAX_POOL=10";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn missing_key_fails() {
+    let expected_error_message = "'MIN_POOL' is not defined.";
+
+    let content = "@gte(\"MIN_POOL\")\nMAX_POOL=10";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::parsing::parse;
+
+#[test]
+fn float_fails() {
+    let expected_error_message = "'3000.5' is not an integer.";
+
+    let content = "@integer\nPORT=3000.5";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        match key.value {
+            crate::parsing::ValueType::Number(v) => assert_eq!(3000.5, v),
+            _ => panic!("Invalid value type. Expected Number"),
+        }
+
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn integer_passes() {
+    let content = "@integer\nPORT=3000";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn number_array_fails() {
+    let expected_error_message = "'2.5' is not an integer.";
+
+    let content = "@integer\nRETRY_DELAYS=[1, 2.5, 3]";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn string_fails() {
+    let expected_error_message = "integer does not support this variable type. integer only supports the number and number array types.";
+
+    let content = "@integer\nPORT=\"3000\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
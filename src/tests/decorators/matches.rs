@@ -137,4 +137,79 @@ fn string_array_passes() {
             }
         }
     }
-}
\ No newline at end of file
+}
+#[test]
+fn case_insensitive_flag_passes() {
+    let content = "@matches(\"^[a-z]+$\", flags=\"i\")\nSOMETHING=\"Something\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn without_flags_is_case_sensitive() {
+    let content = "@matches(\"^[a-z]+$\")\nSOMETHING=\"Something\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+}
+
+#[test]
+fn invalid_flag_fails() {
+    let expected_error_message = "'x' is not valid for 'flags'. 'flags' can only contain 'i' (case-insensitive), 'm' (multiline) and 's' (dot matches new lines).";
+
+    let content = "@matches(\"^[a-z]+$\", flags=\"x\")\nSOMETHING=\"something\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(1, result.errors.len());
+    assert_eq!(expected_error_message, result.errors[0].message);
+}
+
+#[test]
+fn invalid_regex_is_parse_error() {
+    let expected_error_message = "Couldn't parse regex ^[a-z+$: unclosed character class.";
+
+    let content = "@matches(\"^[a-z+$\")\nSOMETHING=\"something\"\n@matches(\"^[a-z+$\")\nOTHER=\"other\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(2, result.errors.len());
+
+    let err = &result.errors[0];
+
+    assert_eq!(expected_error_message, err.message);
+    assert_eq!(1, err.position.line);
+    assert_eq!(10, err.position.column);
+    assert_eq!(9, err.length);
+
+    for key in result.keys {
+        assert!(key.errors.is_empty());
+    }
+}
+
+#[test]
+fn regex_size_limit_is_reported() {
+    let content = "@matches(\"a{9999}{9999}\")\nSOMETHING=\"a\"";
+
+    let result = parse(content);
+
+    assert_eq!(1, result.errors.len());
+    assert!(result.errors[0].message.starts_with("Couldn't parse regex a{9999}{9999}: "));
+    assert!(!result.errors[0].message.ends_with(".."));
+    assert!(!result.errors[0].message.contains('\n'));
+}
@@ -4,7 +4,7 @@ use crate::parsing::parse;
 fn number_fails() {
     let max = 10;
     let expected_key = "SOMETHING";
-    let expected_value = 11.0;
+    let expected_value = 11;
 
     let expected_error_message = format!("{expected_value} is too large. Maximum value is {max}.");
 
@@ -21,10 +21,10 @@ fn number_fails() {
             panic!("'{}' was found. Expected '{expected_key}'", key.key);
         } else {
             match key.value {
-                crate::parsing::ValueType::Number(v) => {
+                crate::parsing::ValueType::Integer(v) => {
                     assert_eq!(expected_value, v);
                 }
-                _ => panic!("Invalid value type. Expected Number"),
+                _ => panic!("Invalid value type. Expected Integer"),
             }
 
             for err in key.errors {
@@ -38,7 +38,7 @@ fn number_fails() {
 fn number_passes() {
     let max = 10;
     let expected_key = "SOMETHING";
-    let expected_value = 10.0;
+    let expected_value = 10;
 
     let content = format!("@max({max})\n{expected_key}={expected_value}");
 
@@ -53,10 +53,10 @@ fn number_passes() {
             panic!("'{}' was found. Expected '{expected_key}'", key.key);
         } else {
             match key.value {
-                crate::parsing::ValueType::Number(v) => {
+                crate::parsing::ValueType::Integer(v) => {
                     assert_eq!(expected_value, v);
                 }
-                _ => panic!("Invalid value type. Expected Number"),
+                _ => panic!("Invalid value type. Expected Integer"),
             }
         }
     }
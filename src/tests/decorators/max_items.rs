@@ -0,0 +1,35 @@
+use crate::parsing::parse;
+
+#[test]
+fn number_array_fails() {
+    let max = 2;
+    let expected_key = "PORTS";
+    let expected_value = vec![8080.0, 8081.0, 8082.0];
+
+    let expected_error_message = format!("Expected at most {max} items but found 3.");
+
+    let content = format!("@maxItems({max})\n{expected_key}={:?}", expected_value);
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn number_array_passes() {
+    let content = "@maxItems(3)\nPORTS=[8080, 8081, 8082]";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
@@ -4,7 +4,7 @@ use crate::parsing::parse;
 fn number_fails() {
     let min = 10;
     let expected_key = "SOMETHING";
-    let expected_value = 9.0;
+    let expected_value = 9;
 
     let expected_error_message = format!("{expected_value} is too small. Minimum value is {min}.");
 
@@ -21,10 +21,10 @@ fn number_fails() {
             panic!("'{}' was found. Expected '{expected_key}'", key.key);
         } else {
             match key.value {
-                crate::parsing::ValueType::Number(v) => {
+                crate::parsing::ValueType::Integer(v) => {
                     assert_eq!(expected_value, v);
                 }
-                _ => panic!("Invalid value type. Expected Number"),
+                _ => panic!("Invalid value type. Expected Integer"),
             }
 
             for err in key.errors {
@@ -38,7 +38,7 @@ fn number_fails() {
 fn number_passes() {
     let min = 10;
     let expected_key = "SOMETHING";
-    let expected_value = 10.0;
+    let expected_value = 10;
 
     let expected_error_message = format!("{expected_value} is too small. Minimum value is {min}.");
 
@@ -50,6 +50,40 @@ fn number_passes() {
         panic!("Result should have been valid.");
     }
 
+    for key in result.keys {
+        if key.key != expected_key {
+            panic!("'{}' was found. Expected '{expected_key}'", key.key);
+        } else {
+            match key.value {
+                crate::parsing::ValueType::Integer(v) => {
+                    assert_eq!(expected_value, v);
+                }
+                _ => panic!("Invalid value type. Expected Integer"),
+            }
+
+            for err in key.errors {
+                assert_eq!(expected_error_message, err.message);
+            }
+        }
+    }
+}
+
+#[test]
+fn float_fails() {
+    let min = 1024;
+    let expected_key = "PORT";
+    let expected_value = 1023.5;
+
+    let expected_error_message = format!("{expected_value} is too small. Minimum value is {min}.");
+
+    let content = format!("@min({min})\n{expected_key}={expected_value}");
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
     for key in result.keys {
         if key.key != expected_key {
             panic!("'{}' was found. Expected '{expected_key}'", key.key);
@@ -266,3 +300,78 @@ fn number_array_passes() {
         }
     }
 }
+
+#[test]
+fn integer_array_fails() {
+    let min = 5;
+    let failed_value = 4;
+    let expected_key = "SOMETHING";
+    let expected_value = vec![failed_value, 5, 5, 5];
+
+    let content = format!("@min({min})\n{expected_key}={:?}", expected_value);
+
+    let expected_error_message = format!("{failed_value} is too small. Minimum value is {min}.");
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        match key.value {
+            crate::parsing::ValueType::IntegerArray(v) => {
+                assert_eq!(expected_value, v);
+            }
+            _ => panic!("Invalid value type. Expected Integer Array"),
+        }
+
+        assert_eq!(1, key.errors.len());
+        assert_eq!(expected_error_message, key.errors[0].message);
+    }
+}
+
+#[test]
+fn string_array_each_false_fails() {
+    let min = 3;
+    let expected_key = "SOMETHING";
+    let expected_value: Vec<&str> = vec!["iiiii", "iiiii"];
+
+    let content = format!("@min({min}, each=false)\n{expected_key}={:?}", expected_value);
+
+    // The number of items is compared instead of the length of each item
+    let expected_error_message = format!("Expected at least {min} items but found 2.");
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        if key.key != expected_key {
+            panic!("'{}' was found. Expected '{expected_key}'", key.key);
+        } else {
+            assert_eq!(1, key.errors.len());
+
+            for err in key.errors {
+                assert_eq!(expected_error_message, err.message);
+            }
+        }
+    }
+}
+
+#[test]
+fn string_array_each_false_passes() {
+    let min = 2;
+    let expected_key = "SOMETHING";
+    let expected_value: Vec<&str> = vec!["i", "i"];
+
+    let content = format!("@min({min}, each=false)\n{expected_key}={:?}", expected_value);
+
+    let result = parse(&content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
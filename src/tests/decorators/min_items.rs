@@ -0,0 +1,65 @@
+use crate::parsing::parse;
+
+#[test]
+fn string_array_fails() {
+    let min = 3;
+    let expected_key = "ALLOWED_ORIGINS";
+    let expected_value = vec!["https://github.com", "https://google.com"];
+
+    let expected_error_message = format!("Expected at least {min} items but found 2.");
+
+    let content = format!("@minItems({min})\n{expected_key}={:?}", expected_value);
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        if key.key != expected_key {
+            panic!("'{}' was found. Expected '{expected_key}'", key.key);
+        } else {
+            match key.value {
+                crate::parsing::ValueType::StringArray(v) => {
+                    assert_eq!(expected_value, v);
+                }
+                _ => panic!("Invalid value type. Expected String Array"),
+            }
+
+            for err in key.errors {
+                assert_eq!(expected_error_message, err.message);
+            }
+        }
+    }
+}
+
+#[test]
+fn single_item_array_passes() {
+    let content = "@minItems(1)\nALLOWED_ORIGINS=[\"https://github.com\"]";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn string_fails() {
+    let expected_error_message = "minItems does not support this variable type. minItems only supports the string array and number array types.";
+
+    let content = "@minItems(1)\nSOMETHING=\"something\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::parsing::parse;
+
+#[test]
+fn empty_array_fails() {
+    let expected_error_message = "Value must not be empty.";
+
+    let content = "@nonEmpty\nALLOWED_ORIGINS=[]";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        match key.value {
+            crate::parsing::ValueType::StringArray(v) => assert!(v.is_empty()),
+            _ => panic!("Invalid value type. Expected String Array"),
+        }
+
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn string_array_passes() {
+    let content = "@nonEmpty\nALLOWED_ORIGINS=[\"https://github.com\"]";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
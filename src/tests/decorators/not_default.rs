@@ -0,0 +1,31 @@
+use crate::parsing::parse;
+
+#[test]
+fn placeholder_fails() {
+    let expected_error_message = "'xxx' looks like a placeholder. Replace it with a real value.";
+
+    let content = "@notDefault\nAPI_KEY=\"xxx\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn real_value_passes() {
+    let content = "@notDefault\nAPI_KEY=\"7fG9qL2xVb8KpZ3wR1tY\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
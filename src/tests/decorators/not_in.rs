@@ -0,0 +1,31 @@
+use crate::parsing::parse;
+
+#[test]
+fn ignores_case() {
+    let expected_error_message = "'Password' is not allowed.";
+
+    let content = "@notIn(\"password\", \"admin\")\nDB_PASSWORD=\"Password\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn other_value_passes() {
+    let content = "@notIn([\"password\", \"admin\"])\nDB_PASSWORD=\"7fG9qL2xVb8KpZ3wR1tY\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
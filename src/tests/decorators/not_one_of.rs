@@ -0,0 +1,59 @@
+use crate::parsing::parse;
+
+#[test]
+fn string_fails() {
+    let expected_key = "PASSWORD";
+    let expected_value = "changeme";
+
+    let expected_error_message =
+        format!("'{expected_value}' is not allowed. Disallowed values are 'password', 'changeme'.");
+
+    let content =
+        format!("@notOneOf(\"password\", \"changeme\")\n{expected_key}=\"{expected_value}\"");
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        if key.key != expected_key {
+            panic!("'{}' was found. Expected '{expected_key}'", key.key);
+        } else {
+            for err in key.errors {
+                assert_eq!(expected_error_message, err.message);
+            }
+        }
+    }
+}
+
+#[test]
+fn string_passes() {
+    let content = "@notOneOf(\"password\", \"changeme\")\nPASSWORD=\"hunter2\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn number_array_fails() {
+    let expected_error_message = "22 is not allowed. Disallowed values are 22, 23.";
+
+    let content = "@notOneOf(22, 23)\nPORTS=[80, 22]";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
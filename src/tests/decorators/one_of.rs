@@ -0,0 +1,101 @@
+use crate::parsing::parse;
+
+#[test]
+fn string_fails() {
+    let expected_key = "LOG_LEVEL";
+    let expected_value = "wrn";
+
+    let expected_error_message = format!(
+        "'{expected_value}' is not one of 'debug', 'info', 'warn', 'error'. Did you mean 'warn'?"
+    );
+
+    let content = format!(
+        "@oneOf(\"debug\", \"info\", \"warn\", \"error\")\n{expected_key}=\"{expected_value}\""
+    );
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        if key.key != expected_key {
+            panic!("'{}' was found. Expected '{expected_key}'", key.key);
+        } else {
+            assert_eq!(1, key.errors.len());
+
+            for err in key.errors {
+                assert_eq!(expected_error_message, err.message);
+            }
+        }
+    }
+}
+
+#[test]
+fn string_passes() {
+    let content = "@oneOf(\"debug\", \"info\", \"warn\", \"error\")\nLOG_LEVEL=\"info\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn number_fails() {
+    let expected_key = "WORKERS";
+    let expected_value = 3.0;
+
+    let expected_error_message = format!("{expected_value} is not one of 1, 2, 4.");
+
+    let content = format!("@oneOf(1, 2, 4)\n{expected_key}={expected_value}");
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn string_array_fails() {
+    let failed_value = "mars";
+    let expected_value = vec!["us-east", failed_value, "eu-west"];
+
+    let expected_error_message = format!("'{failed_value}' is not one of 'us-east', 'eu-west'.");
+
+    let content = format!("@oneOf(\"us-east\", \"eu-west\")\nREGIONS={:?}", expected_value);
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        assert_eq!(1, key.errors.len());
+
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn number_array_passes() {
+    let content = "@oneOf(80, 443, 8080)\nPORTS=[80, 443]";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
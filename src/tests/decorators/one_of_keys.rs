@@ -0,0 +1,32 @@
+use crate::parsing::parse;
+
+#[test]
+fn both_fail() {
+    let expected_error_message =
+        "Exactly one of 'DATABASE_URL', 'DATABASE_HOST' must have a value but 'DATABASE_URL', 'DATABASE_HOST' do.";
+
+    let content = "@oneOfKeys(\"DATABASE_HOST\")\nDATABASE_URL=\"postgres://localhost\"\nDATABASE_HOST=\"localhost\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn one_passes() {
+    let content = "@oneOfKeys(\"DATABASE_HOST\")\nDATABASE_URL=\"postgres://localhost\"\nDATABASE_HOST=\"\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
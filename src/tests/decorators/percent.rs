@@ -0,0 +1,31 @@
+use crate::parsing::parse;
+
+#[test]
+fn within_bounds_passes() {
+    let content = "@percent(min=\"0%\", max=\"100%\")\nSAMPLE_RATE=5%";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn too_large_fails() {
+    let expected_error_message = "'150%' is too large. Maximum value is 100%.";
+
+    let content = "@percent(max=\"100%\")\nSAMPLE_RATE=150%";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
@@ -3,7 +3,7 @@ use crate::parsing::{Scope, parse};
 #[test]
 fn correct_scope() {
     let expected_key = "SOMETHING";
-    let expected_value = 9.0;
+    let expected_value = 9;
 
     let content = format!("@private\n{expected_key}={expected_value}");
 
@@ -14,10 +14,10 @@ fn correct_scope() {
             panic!("'{}' was found. Expected '{expected_key}'", key.key);
         } else {
             match key.value {
-                crate::parsing::ValueType::Number(v) => {
+                crate::parsing::ValueType::Integer(v) => {
                     assert_eq!(expected_value, v);
                 }
-                _ => panic!("Invalid value type. Expected Number"),
+                _ => panic!("Invalid value type. Expected Integer"),
             }
 
             match key.scope {
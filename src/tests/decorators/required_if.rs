@@ -0,0 +1,48 @@
+use crate::parsing::parse;
+
+#[test]
+fn empty_fails() {
+    let expected_key = "TLS_CERT";
+
+    let expected_error_message = format!("'{expected_key}' is required when 'TLS_ENABLED' is true.");
+
+    let content = format!("TLS_ENABLED=true\n@requiredIf(\"TLS_ENABLED\", true)\n{expected_key}=\"\"");
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        if key.key == expected_key {
+            assert_eq!(1, key.errors.len());
+
+            for err in key.errors {
+                assert_eq!(expected_error_message, err.message);
+            }
+        }
+    }
+}
+
+#[test]
+fn empty_passes_when_condition_false() {
+    let content = "TLS_ENABLED=false\n@requiredIf(\"TLS_ENABLED\", true)\nTLS_CERT=\"\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
+
+#[test]
+fn value_passes() {
+    let content = "TLS_ENABLED=true\n@requiredIf(\"TLS_ENABLED\", true)\nTLS_CERT=\"/etc/cert.pem\"";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
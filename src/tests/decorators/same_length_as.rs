@@ -0,0 +1,36 @@
+use crate::parsing::parse;
+
+#[test]
+fn number_array_fails() {
+    let expected_key = "PORTS";
+
+    let expected_error_message =
+        format!("'{expected_key}' has a length of 2 but 'HOSTS' has a length of 3.");
+
+    let content = format!("HOSTS=[\"a\", \"b\", \"c\"]\n@sameLengthAs(\"HOSTS\")\n{expected_key}=[80, 443]");
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        if key.key == expected_key {
+            for err in key.errors {
+                assert_eq!(expected_error_message, err.message);
+            }
+        }
+    }
+}
+
+#[test]
+fn number_array_passes() {
+    let content = "HOSTS=[\"a\", \"b\"]\n@sameLengthAs(\"HOSTS\")\nPORTS=[80, 443]";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
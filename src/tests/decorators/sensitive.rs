@@ -0,0 +1,23 @@
+use crate::parsing::{config::Cloak, parse};
+
+#[test]
+fn marks_key_sensitive() {
+    let result = parse("@sensitive\nAPI_KEY=\"abc\"\nPORT=80");
+
+    assert!(result.keys[0].valid);
+    assert!(result.keys[0].is_sensitive());
+    assert!(!result.keys[1].is_sensitive());
+}
+
+#[test]
+fn sensitive_keys_are_always_cloaked() {
+    let result = parse("@public\n@sensitive\nAPI_KEY=\"abc\"\n@public\nURL=\"x\"");
+
+    for cloak in [Cloak::Off, Cloak::Private, Cloak::All] {
+        assert!(cloak.hides(&result.keys[0]));
+    }
+
+    assert!(!Cloak::Off.hides(&result.keys[1]));
+    assert!(!Cloak::Private.hides(&result.keys[1]));
+    assert!(Cloak::All.hides(&result.keys[1]));
+}
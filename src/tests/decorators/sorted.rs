@@ -0,0 +1,31 @@
+use crate::parsing::parse;
+
+#[test]
+fn string_array_fails() {
+    let expected_error_message = "'a' is out of order. Items must be sorted in ascending order.";
+
+    let content = "@sorted\nHOSTS=[\"b\", \"a\", \"c\"]";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn number_array_descending_passes() {
+    let content = "@sorted(\"desc\")\nPRIORITIES=[30, 20, 10]";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
@@ -0,0 +1,36 @@
+use crate::parsing::parse;
+
+#[test]
+fn number_array_fails() {
+    let duplicate = 8080.0;
+    let expected_value = vec![duplicate, 8081.0, duplicate, duplicate];
+
+    let expected_error_message = format!("'{duplicate}' appears more than once.");
+
+    let content = format!("@uniqueItems\nPORTS={:?}", expected_value);
+
+    let result = parse(&content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    for key in result.keys {
+        assert_eq!(1, key.errors.len());
+
+        for err in key.errors {
+            assert_eq!(expected_error_message, err.message);
+        }
+    }
+}
+
+#[test]
+fn string_array_passes() {
+    let content = "@uniqueItems\nHOSTS=[\"a\", \"b\", \"c\"]";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+}
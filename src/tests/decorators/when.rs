@@ -0,0 +1,121 @@
+use crate::diagnostics;
+use crate::parsing::{parse, Environment};
+
+/// Returns the names of the resolved keys
+fn resolved(content: &str, environment: Environment) -> Vec<String> {
+    parse(content)
+        .resolve(&environment)
+        .into_iter()
+        .map(|k| k.key)
+        .collect()
+}
+
+#[test]
+fn includes_keys_when_condition_met() {
+    let content = "AUTH_PROVIDER=\"okta\"\n@when(\"AUTH_PROVIDER\", \"okta\")\nOKTA_DOMAIN=\"a.okta.com\"\n@when(\"AUTH_PROVIDER\", \"auth0\")\nAUTH0_DOMAIN=\"a.auth0.com\"";
+
+    assert!(parse(content).valid);
+    assert_eq!(vec!["AUTH_PROVIDER", "OKTA_DOMAIN"], resolved(content, Environment::Dev));
+}
+
+#[test]
+fn compares_numbers_and_booleans() {
+    let content = "WORKERS=4\nTLS=true\n@when(\"WORKERS\", 4)\n@when(\"TLS\", true)\nPOOL=\"x\"";
+
+    assert_eq!(vec!["WORKERS", "TLS", "POOL"], resolved(content, Environment::Dev));
+
+    let content = "WORKERS=4\nTLS=false\n@when(\"WORKERS\", 4)\n@when(\"TLS\", true)\nPOOL=\"x\"";
+
+    assert_eq!(vec!["WORKERS", "TLS"], resolved(content, Environment::Dev));
+}
+
+#[test]
+fn uses_keys_of_the_environment() {
+    let content = "AUTH=\"none\"\n@prod\nAUTH=\"okta\"\n@when(\"AUTH\", \"okta\")\nOKTA_DOMAIN=\"a.okta.com\"";
+
+    assert_eq!(vec!["AUTH"], resolved(content, Environment::Dev));
+    assert_eq!(vec!["AUTH", "OKTA_DOMAIN"], resolved(content, Environment::Prod));
+}
+
+#[test]
+fn skips_keys_depending_on_skipped_keys() {
+    let content = "A=\"off\"\n@when(\"A\", \"on\")\nB=\"on\"\n@when(\"B\", \"on\")\nC=1";
+
+    assert_eq!(vec![true, false, false], parse(content).active(&Environment::Dev));
+}
+
+#[test]
+fn falls_back_to_keys_in_all_environments() {
+    let content = "FEATURE=\"off\"\nPORT=3000\n@prod\n@when(\"FEATURE\", \"on\")\nPORT=8080";
+
+    let keys = parse(content).resolve(&Environment::Prod);
+
+    assert_eq!("3000", keys[1].value.to_string());
+}
+
+#[test]
+fn ignores_errors_of_skipped_keys() {
+    let content = "AUTH=\"none\"\n@when(\"AUTH\", \"okta\")\n@startsWith(\"https\")\nOKTA_URL=\"http://a.okta.com\"";
+
+    let result = parse(content);
+
+    assert!(!result.keys[1].valid);
+    assert!(result.valid);
+    assert!(diagnostics::collect(&result, &Environment::Dev).is_empty());
+}
+
+#[test]
+fn ignores_skipped_referenced_keys() {
+    let content = "TLS=\"off\"\n@when(\"TLS\", \"on\")\nMIN_POOL=100\n@gte(\"MIN_POOL\")\nMAX_POOL=5\n@when(\"TLS\", \"on\")\nCERT_REQUIRED=true\n@requiredIf(\"CERT_REQUIRED\", true)\nCERT=\"\"";
+
+    let result = parse(content);
+
+    assert!(result.valid);
+    assert!(result.keys.iter().all(|k| k.errors.is_empty()));
+}
+
+#[test]
+fn compares_keys_in_the_environments_they_are_active() {
+    let content = "TLS=\"off\"\n@prod\nTLS=\"on\"\n@when(\"TLS\", \"on\")\nMIN_POOL=100\n@gte(\"MIN_POOL\")\nMAX_POOL=5";
+
+    let result = parse(content);
+
+    assert!(!result.valid);
+    assert_eq!(1, result.keys[3].errors.len());
+    assert_eq!("5 must be greater than or equal to MIN_POOL (100).", result.keys[3].errors[0].message);
+}
+
+#[test]
+fn compares_against_defined_keys_only() {
+    let result = parse("@gte(\"MISSING\")\nMAX_POOL=5");
+
+    assert!(!result.valid);
+    assert_eq!("'MISSING' is not defined.", result.keys[0].errors[0].message);
+}
+
+#[test]
+fn undefined_key_fails() {
+    let result = parse("@when(\"MISSING\", 1)\nX=1");
+
+    assert!(!result.valid);
+    assert_eq!("'MISSING' is not defined.", result.errors[0].message);
+    assert_eq!("invalid-argument", result.errors[0].code);
+}
+
+#[test]
+fn own_key_fails() {
+    let result = parse("@when(\"X\", 1)\nX=1");
+
+    assert_eq!("'X' can't be a condition of itself.", result.errors[0].message);
+}
+
+#[test]
+fn no_value_fails() {
+    let result = parse("A=1\n@when(\"A\")\nX=1");
+
+    assert!(!result.valid);
+    assert_eq!(
+        "The when decorator requires a key and a value to be provided with it. Ex: `@when(\"AUTH_PROVIDER\", \"okta\")`",
+        result.keys[1].errors[0].message
+    );
+}
@@ -0,0 +1,321 @@
+use crate::diagnostics::{Format, LineIndex, Renderer, Severity, Span};
+use crate::parsing::{config::Cloak, parse, FilePosition};
+
+#[test]
+fn line_index_converts_positions() {
+    let content = "A=\"é\"\nB=2";
+
+    let index = LineIndex::new(content);
+
+    let position = FilePosition { line: 2, column: 3 };
+
+    assert_eq!(9, index.offset(position));
+    assert_eq!(position, index.position(9));
+    assert_eq!("\"é\"", index.text(index.span(FilePosition { line: 1, column: 3 }, 3)));
+}
+
+#[test]
+fn key_errors_point_at_the_invalid_item() {
+    let content = "@min(1024)\nPORTS=[2048, 80]";
+
+    let result = parse(content);
+
+    let err = &result.keys[0].errors[0];
+
+    assert_eq!(Severity::Error, err.severity);
+    assert_eq!("min", err.code);
+    assert_eq!(Some("PORTS".to_owned()), err.key);
+    assert_eq!("80", &content[err.span.start..err.span.end]);
+    assert_eq!(FilePosition { line: 2, column: 14 }, err.position);
+
+    assert_eq!(1, err.labels.len());
+    assert_eq!("@min(1024)", &content[err.labels[0].span.start..err.labels[0].span.end]);
+}
+
+#[test]
+fn key_errors_point_at_items_contained_in_other_items() {
+    let content = "@min(1024)\nPORTS=[8080, 80]";
+
+    let result = parse(content);
+
+    let err = &result.keys[0].errors[0];
+
+    assert_eq!(Span::new(24, 26), err.span);
+    assert_eq!(FilePosition { line: 2, column: 14 }, err.position);
+}
+
+#[test]
+fn key_errors_point_at_each_repeated_item() {
+    let content = "@startsWith(\"https://\")\nHOSTS=[\n  \"a.com\",\n  \"a.com\"\n]";
+
+    let result = parse(content);
+
+    let errors = &result.keys[0].errors;
+
+    assert_eq!(2, errors.len());
+    assert_eq!(FilePosition { line: 3, column: 3 }, errors[0].position);
+    assert_eq!(FilePosition { line: 4, column: 3 }, errors[1].position);
+    assert_eq!("\"a.com\"", &content[errors[1].span.start..errors[1].span.end]);
+}
+
+#[test]
+fn records_item_and_argument_spans() {
+    let content = "@matches(\"^[a-z]+$\", flags=\"i\")\nNAMES=[\"a\", \"b, c\"]";
+
+    let result = parse(content);
+
+    let key = &result.keys[0];
+
+    let items: Vec<&str> = key.item_spans.iter().map(|s| &content[s.start..s.end]).collect();
+    assert_eq!(vec!["\"a\"", "\"b, c\""], items);
+
+    let decorator = &key.decorators[0];
+    assert_eq!("@matches(\"^[a-z]+$\", flags=\"i\")", &content[decorator.span.start..decorator.span.end]);
+
+    let arguments: Vec<&str> = decorator
+        .arguments
+        .list
+        .iter()
+        .map(|a| &content[a.span.start..a.span.end])
+        .collect();
+    assert_eq!(vec!["\"^[a-z]+$\"", "flags=\"i\""], arguments);
+}
+
+#[test]
+fn unknown_decorators_suggest_a_fix() {
+    let content = "@mni(5)\nPORT=3000";
+
+    let result = parse(content);
+
+    let err = &result.errors[0];
+
+    assert_eq!("unknown-decorator", err.code);
+
+    let fix = err.fix.as_ref().expect("Expected a fix to be suggested.");
+
+    assert_eq!("@min", fix.replacement);
+    assert_eq!(Span::new(0, 4), fix.span);
+}
+
+#[test]
+fn duplicate_keys_are_warnings() {
+    let content = "PORT=3000\nPORT=4000";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid.");
+    }
+
+    assert_eq!(1, result.warnings.len());
+
+    let warning = &result.warnings[0];
+
+    assert_eq!(Severity::Warning, warning.severity);
+    assert_eq!(2, warning.position.line);
+    assert_eq!(Span::new(0, 4), warning.labels[0].span);
+}
+
+#[test]
+fn renders_json_and_lsp() {
+    let content = "@min(10)\nSECRET=\"abc\"";
+
+    let result = parse(content);
+
+    let renderer = Renderer::new(content, ".vnv");
+
+    let json: serde_json::Value =
+        serde_json::from_str(&renderer.render(Format::Json, &result.keys[0].errors)).unwrap();
+
+    assert_eq!(".vnv", json["file"]);
+    assert_eq!("min", json["diagnostics"][0]["code"]);
+    assert_eq!(16, json["diagnostics"][0]["span"]["start"]);
+
+    let lsp: serde_json::Value =
+        serde_json::from_str(&renderer.render(Format::Lsp, &result.keys[0].errors)).unwrap();
+
+    assert_eq!(1, lsp[0]["severity"]);
+    assert_eq!(1, lsp[0]["range"]["start"]["line"]);
+    assert_eq!(7, lsp[0]["range"]["start"]["character"]);
+    assert_eq!(0, lsp[0]["relatedInformation"][0]["location"]["range"]["start"]["line"]);
+}
+
+#[test]
+fn redacts_values() {
+    colored::control::set_override(false);
+
+    let content = "@min(10)\nSECRET=\"abc\"";
+
+    let result = parse(content);
+
+    let renderer = Renderer::new(content, ".vnv").redact(vec![result.keys[0].value_span]);
+
+    let output = renderer.terminal(&result.keys[0].errors[0]);
+
+    assert!(!output.contains("abc"));
+    assert!(output.contains("SECRET=*****"));
+}
+
+#[test]
+fn renders_lines_past_99() {
+    colored::control::set_override(false);
+
+    let content = format!("{}@min(100)\nPORT=80", "\n".repeat(99));
+
+    let result = parse(&content);
+
+    let renderer = Renderer::new(&content, ".vnv");
+
+    let output = renderer.terminal(&result.keys[0].errors[0]);
+
+    assert!(output.contains("--> .vnv:101:6\n      |\n"));
+    assert!(output.contains("100   |  @min(100)\n"));
+    assert!(output.contains("101   |  PORT=80\n      |       ^^\n"));
+
+    let content = "@min(100)\nPORT=80";
+
+    let result = parse(content);
+
+    let output = Renderer::new(content, ".vnv").terminal(&result.keys[0].errors[0]);
+
+    assert!(output.contains(" 2   |  PORT=80\n     |       ^^\n"));
+}
+
+#[test]
+fn cloaks_private_keys() {
+    colored::control::set_override(false);
+
+    let content = "@min(100)\nPORT=80\n@public\n@min(100)\nPUBLIC_PORT=80";
+
+    let result = parse(content);
+
+    let renderer = Renderer::new(content, ".vnv").cloak(&result.keys, Cloak::Private, 0);
+
+    let private = renderer.terminal(&result.keys[0].errors[0]);
+    let public = renderer.terminal(&result.keys[1].errors[0]);
+
+    assert!(private.contains("** is too small. Minimum value is 100."));
+    assert!(private.contains("PORT=**"));
+    // PORT has the same value but it isn't part of the diagnostic about PUBLIC_PORT
+    assert!(public.contains("80 is too small. Minimum value is 100."));
+    assert!(public.contains("PUBLIC_PORT=80"));
+}
+
+#[test]
+fn cloaks_partially() {
+    colored::control::set_override(false);
+
+    let content = "@endsWith(\"x\")\nTOKEN=\"sk_live_abcdef123\"";
+
+    let result = parse(content);
+
+    let renderer = Renderer::new(content, ".vnv").cloak(&result.keys, Cloak::All, 2);
+
+    let output = renderer.terminal(&result.keys[0].errors[0]);
+
+    assert!(output.contains("TOKEN=\"sk*************23\""));
+    assert!(output.contains("'sk*************23' does not end with 'x'"));
+    assert!(!output.contains("live"));
+}
+
+#[test]
+fn cloaks_every_format() {
+    let content = "@startsWith(\"https\")\nURL=\"http://example.com\"";
+
+    let result = parse(content);
+
+    let renderer = Renderer::new(content, ".vnv").cloak(&result.keys, Cloak::All, 0);
+
+    for format in [Format::Json, Format::Lsp] {
+        let output = renderer.render(format, &result.keys[0].errors);
+
+        assert!(!output.contains("example"), "{output}");
+    }
+}
+
+#[test]
+fn cloaks_whole_values_in_messages() {
+    let content = "@sensitive\nA=80\n@min(10000)\nB=8080";
+
+    let result = parse(content);
+
+    // A diagnostic that isn't about a key so every hidden value applies to it
+    let diagnostic = crate::diagnostics::Diagnostic::error("test", "8080 is not 80.");
+
+    let renderer = Renderer::new(content, ".vnv").cloak(&result.keys, Cloak::Off, 0);
+
+    let output = renderer.render(Format::Json, &[diagnostic]);
+
+    assert!(output.contains("8080 is not **."));
+
+    // Only whole values are replaced so B's value still shows
+    let output = renderer.render(Format::Json, &result.keys[1].errors);
+
+    assert!(output.contains("8080 is too small"));
+}
+
+#[test]
+fn cloaks_referenced_keys() {
+    colored::control::set_override(false);
+
+    let content = "MIN_POOL=12345\n@public\n@gte(\"MIN_POOL\")\nMAX_POOL=5";
+
+    let result = parse(content);
+
+    let errors = &result.keys[1].errors;
+
+    assert!(errors[0].message.contains("12345"));
+
+    // MIN_POOL is private so its value is hidden in the messages about MAX_POOL
+    let renderer = Renderer::new(content, ".vnv").cloak(&result.keys, Cloak::Private, 0);
+
+    let terminal = renderer.render(Format::Terminal, errors);
+    let json = renderer.render(Format::Json, errors);
+    let lsp = renderer.render(Format::Lsp, errors);
+
+    for output in [&terminal, &json, &lsp] {
+        assert!(!output.contains("12345"));
+    }
+
+    assert!(terminal.contains("MIN_POOL (*****)"));
+    assert!(terminal.contains("MAX_POOL=5"));
+}
+
+#[test]
+fn cloaks_array_items() {
+    colored::control::set_override(false);
+
+    let content = "@startsWith(\"sk_\")\nKEYS=[\"hunter2\", \"sk_ok\"]";
+
+    let result = parse(content);
+
+    let renderer = Renderer::new(content, ".vnv").cloak(&result.keys, Cloak::All, 0);
+
+    for format in [Format::Terminal, Format::Json, Format::Lsp] {
+        let output = renderer.render(format, &result.keys[0].errors);
+
+        assert!(!output.contains("hunter2"), "{output}");
+    }
+
+    let output = renderer.terminal(&result.keys[0].errors[0]);
+
+    assert!(output.contains("'*******' does not start with 'sk_'"));
+    assert!(output.contains("KEYS=*****"));
+    assert!(!output.contains("sk_ok"));
+}
+
+#[test]
+fn cloaks_only_related_keys() {
+    colored::control::set_override(false);
+
+    let content = "RETRIES=5\n@public\n@min(5)\nWORKERS=3";
+
+    let result = parse(content);
+
+    let renderer = Renderer::new(content, ".vnv").cloak(&result.keys, Cloak::Private, 0);
+
+    let output = renderer.terminal(&result.keys[1].errors[0]);
+
+    // RETRIES is hidden but has nothing to do with WORKERS so the 5 in the message stays
+    assert!(output.contains("3 is too small. Minimum value is 5."));
+}
@@ -0,0 +1,37 @@
+use crate::parsing::{parse, Environment};
+
+#[test]
+fn resolve_prefers_environment_specific_key() {
+    let content = "PORT=3000\n@prod\nPORT=8080";
+
+    let result = parse(content);
+
+    let keys = result.resolve(&Environment::Prod);
+
+    assert_eq!(1, keys.len());
+    assert_eq!(Environment::Prod, keys[0].environment);
+
+    let keys = result.resolve(&Environment::Dev);
+
+    assert_eq!(1, keys.len());
+    assert_eq!(Environment::All, keys[0].environment);
+}
+
+#[test]
+fn resolve_skips_other_environment() {
+    let content = "@dev\nAPI_KEY=\"secret\"\nPORT=3000";
+
+    let result = parse(content);
+
+    let keys = result.resolve(&Environment::Prod);
+
+    assert_eq!(1, keys.len());
+    assert_eq!("PORT", keys[0].key);
+}
+
+#[test]
+fn environment_from_str() {
+    assert_eq!(Ok(Environment::Dev), "dev".parse());
+    assert_eq!(Ok(Environment::Prod), "production".parse());
+    assert!("staging".parse::<Environment>().is_err());
+}
@@ -0,0 +1,80 @@
+use crate::util::files::{self, can_overwrite, is_generated, write_atomic};
+use crate::tests::common::temp_dir;
+use std::fs;
+
+#[test]
+fn detects_generated_files() {
+    assert!(is_generated(&format!("{}\nPORT=80\n", files::header(".vnv"))));
+    assert!(is_generated("# This file was generated from 'config/app.vnv' by vnv.\r\n"));
+    assert!(!is_generated("# This file was generated by hand.\nPORT=80\n"));
+    assert!(!is_generated("PORT=80\n# This file was generated from '.vnv' by vnv.\n"));
+}
+
+#[test]
+fn overwrites_missing_empty_and_generated_files() {
+    let dir = temp_dir("files", "overwrite");
+
+    assert!(can_overwrite(&dir.join(".env")));
+
+    fs::write(dir.join(".env"), "\n").unwrap();
+    assert!(can_overwrite(&dir.join(".env")));
+
+    fs::write(dir.join(".env"), files::header(".vnv")).unwrap();
+    assert!(can_overwrite(&dir.join(".env")));
+
+    fs::write(dir.join(".env"), "SECRET=hand-written\n").unwrap();
+    assert!(!can_overwrite(&dir.join(".env")));
+}
+
+#[test]
+fn writes_atomically() {
+    let dir = temp_dir("files", "atomic");
+    let path = dir.join(".env");
+
+    fs::write(&path, "OLD=1\n").unwrap();
+
+    write_atomic(&path, "NEW=1\n", false).unwrap();
+
+    assert_eq!("NEW=1\n", fs::read_to_string(&path).unwrap());
+
+    // The temporary file is renamed over the output
+    let entries: Vec<String> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+
+    assert_eq!(vec![".env"], entries);
+}
+
+#[test]
+fn failed_writes_keep_the_file() {
+    let dir = temp_dir("files", "failed");
+
+    // A directory can't be replaced by a file
+    fs::create_dir(dir.join(".env")).unwrap();
+
+    assert!(write_atomic(&dir.join(".env"), "PORT=80\n", false).is_err());
+    assert!(dir.join(".env").is_dir());
+    assert_eq!(1, fs::read_dir(&dir).unwrap().count());
+}
+
+#[cfg(unix)]
+#[test]
+fn private_files_are_owner_only() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = temp_dir("files", "private");
+    let path = dir.join(".env");
+
+    fs::write(&path, "OLD=1\n").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+    write_atomic(&path, "SECRET=1\n", true).unwrap();
+
+    assert_eq!(0o600, fs::metadata(&path).unwrap().permissions().mode() & 0o777);
+
+    write_atomic(&path, "PUBLIC=1\n", false).unwrap();
+
+    // Public builds keep the permissions of the file they replace
+    assert_eq!(0o600, fs::metadata(&path).unwrap().permissions().mode() & 0o777);
+}
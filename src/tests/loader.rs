@@ -0,0 +1,105 @@
+use crate::parsing::{Environment, ValueType};
+use crate::{load, load_str};
+use std::env;
+
+#[test]
+fn loads_resolved_keys() {
+    let content = "PORT=3000\n@prod\nPORT=8080\n@dev\nDEBUG=1\nHOSTS=[\"a.com\", \"b.com\"]";
+
+    let loaded = match load_str(content, Environment::Prod) {
+        Ok(loaded) => loaded,
+        Err(_) => panic!("Result should have been valid."),
+    };
+
+    match loaded.get("PORT") {
+        Some(ValueType::Integer(v)) => assert_eq!(8080, *v),
+        _ => panic!("Invalid value type. Expected Integer"),
+    }
+
+    assert!(loaded.get("DEBUG").is_none());
+
+    assert_eq!(
+        vec![
+            ("PORT".to_owned(), "8080".to_owned()),
+            ("HOSTS".to_owned(), "a.com,b.com".to_owned())
+        ],
+        loaded.vars()
+    );
+}
+
+#[test]
+fn returns_diagnostics() {
+    let expected_error_message = "80 is too small. Minimum value is 1024.";
+
+    let content = "@min(1024)\nPORT=80";
+
+    let diagnostics = match load_str(content, Environment::Prod) {
+        Ok(_) => panic!("Result should have been invalid."),
+        Err(diagnostics) => diagnostics,
+    };
+
+    assert_eq!(1, diagnostics.len());
+    assert_eq!(expected_error_message, diagnostics[0].message);
+    assert_eq!(Some("PORT".to_owned()), diagnostics[0].key);
+    assert_eq!(2, diagnostics[0].position.line);
+}
+
+#[test]
+fn ignores_errors_in_other_environments() {
+    let content = "@dev\n@min(1024)\nPORT=80\n@prod\nPORT=8080";
+
+    if load_str(content, Environment::Prod).is_err() {
+        panic!("Result should have been valid.");
+    }
+
+    if load_str(content, Environment::Dev).is_ok() {
+        panic!("Result should have been invalid.");
+    }
+}
+
+#[test]
+fn set_env_does_not_override() {
+    env::set_var("VNV_LOADER_TEST_EXISTING", "existing");
+
+    let content = "VNV_LOADER_TEST_EXISTING=\"new\"\nVNV_LOADER_TEST_NEW=\"new\"";
+
+    let loaded = match load_str(content, Environment::Dev) {
+        Ok(loaded) => loaded,
+        Err(_) => panic!("Result should have been valid."),
+    };
+
+    loaded.set_env();
+
+    assert_eq!("existing", env::var("VNV_LOADER_TEST_EXISTING").unwrap());
+    assert_eq!("new", env::var("VNV_LOADER_TEST_NEW").unwrap());
+
+    loaded.set_env_override();
+
+    assert_eq!("new", env::var("VNV_LOADER_TEST_EXISTING").unwrap());
+}
+
+#[test]
+fn missing_file_fails() {
+    match load("does-not-exist.vnv", Environment::Dev) {
+        Ok(_) => panic!("Result should have been invalid."),
+        Err(diagnostics) => assert!(diagnostics[0]
+            .message
+            .starts_with("Couldn't read 'does-not-exist.vnv'")),
+    }
+}
+
+#[test]
+fn accepts_previous_names() {
+    env::set_var("VNV_TEST_OLD_DB_URL", "postgres://old");
+
+    let content = "@alias(\"VNV_TEST_OLD_DB_URL\")\nVNV_TEST_DATABASE_URL=\"postgres://localhost\"";
+
+    let loaded = load_str(content, Environment::Dev).unwrap();
+
+    assert_eq!(1, loaded.warnings.len());
+    assert_eq!("renamed-key", loaded.warnings[0].code);
+
+    loaded.set_env();
+
+    assert_eq!("postgres://old", env::var("VNV_TEST_DATABASE_URL").unwrap());
+}
@@ -0,0 +1,106 @@
+use crate::parsing::{
+    config::{Naming, Prefixes},
+    naming, parse,
+};
+
+fn check(content: &str, naming: &Naming) -> Vec<(String, String)> {
+    let mut result = parse(content);
+
+    naming::apply(&mut result, content, naming);
+
+    result
+        .keys
+        .iter()
+        .flat_map(|k| k.errors.iter())
+        .map(|e| (e.code.to_owned(), e.fix.as_ref().unwrap().replacement.to_owned()))
+        .collect()
+}
+
+#[test]
+fn posix_keys_are_required() {
+    let content = "1PORT=3000\nMY KEY=1\napi-url=\"x\"\nvalid_Key=1";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    let fixes: Vec<&str> = result
+        .keys
+        .iter()
+        .flat_map(|k| k.errors.iter())
+        .map(|e| e.fix.as_ref().unwrap().replacement.as_str())
+        .collect();
+
+    assert_eq!(vec!["_1PORT", "MY_KEY", "api_url"], fixes);
+    assert!(result.keys[3].valid);
+}
+
+#[test]
+fn screaming_snake_case() {
+    let naming = Naming {
+        screaming_snake_case: true,
+        ..Naming::default()
+    };
+
+    let errors = check("apiUrl=1\nHTTPServer=2\nAPI__URL=3\nAPI_URL=4\n_PRIVATE=5", &naming);
+
+    assert_eq!(
+        vec![
+            ("key-case".to_owned(), "API_URL".to_owned()),
+            ("key-case".to_owned(), "HTTP_SERVER".to_owned()),
+            ("key-case".to_owned(), "API_URL".to_owned()),
+        ],
+        errors
+    );
+}
+
+#[test]
+fn prefixes_by_scope() {
+    let naming = Naming {
+        prefixes: Prefixes {
+            public: Some(String::from("PUBLIC_")),
+            private: None,
+        },
+        ..Naming::default()
+    };
+
+    let errors = check("@public\nHOST=1\n@public\nPUBLIC_PORT=2\nSECRET=3", &naming);
+
+    assert_eq!(vec![("key-prefix".to_owned(), "PUBLIC_HOST".to_owned())], errors);
+}
+
+#[test]
+fn forbidden_names() {
+    let naming = Naming {
+        forbidden: vec![String::from("PATH")],
+        ..Naming::default()
+    };
+
+    let errors = check("path=1\nHOME=2", &naming);
+
+    assert_eq!(vec![("forbidden-key".to_owned(), "APP_path".to_owned())], errors);
+}
+
+#[test]
+fn fixes_follow_the_whole_policy() {
+    let naming = Naming {
+        screaming_snake_case: true,
+        prefixes: Prefixes {
+            public: Some(String::from("PUBLIC_")),
+            private: None,
+        },
+        forbidden: Vec::new(),
+    };
+
+    let errors = check("@public\napiUrl=1", &naming);
+
+    assert_eq!(
+        vec![
+            ("key-case".to_owned(), "PUBLIC_API_URL".to_owned()),
+            ("key-prefix".to_owned(), "PUBLIC_API_URL".to_owned()),
+        ],
+        errors
+    );
+}
@@ -0,0 +1,187 @@
+use crate::parsing::{parse, FilePosition};
+
+#[test]
+fn unterminated_string() {
+    let content = "NAME=\"abc\nPORT=3000";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!(1, result.errors.len());
+    assert_eq!("unterminated-string", result.errors[0].code);
+    assert_eq!(FilePosition { line: 1, column: 6 }, result.errors[0].position);
+
+    // Parsing recovers at the next line
+    assert_eq!(1, result.keys.len());
+    assert_eq!("PORT", result.keys[0].key);
+}
+
+#[test]
+fn unterminated_array() {
+    let content = "HOSTS=[\"a.com\",\n  \"b.com\"\nPORT=3000";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    let codes: Vec<&str> = result.errors.iter().map(|e| e.code.as_str()).collect();
+
+    assert_eq!(vec!["unterminated-array", "missing-equals"], codes);
+    assert_eq!(FilePosition { line: 1, column: 7 }, result.errors[0].position);
+
+    assert_eq!(1, result.keys.len());
+    assert_eq!("PORT", result.keys[0].key);
+}
+
+#[test]
+fn unterminated_array_at_end_of_file() {
+    let content = "PORT=3000\nHOSTS=[\"a.com\"";
+
+    let result = parse(content);
+
+    assert_eq!(1, result.errors.len());
+    assert_eq!("unterminated-array", result.errors[0].code);
+    assert_eq!(1, result.keys.len());
+}
+
+#[test]
+fn multi_line_arrays_are_valid() {
+    let content = "HOSTS=[\n  \"a.com\",\n  \"b.com\"\n]\nPORT=3000";
+
+    let result = parse(content);
+
+    if !result.valid {
+        panic!("Result should have been valid. {:?}", result.errors);
+    }
+
+    assert_eq!(2, result.keys.len());
+}
+
+#[test]
+fn decorator_without_key() {
+    let content = "PORT=3000\n@min(1)";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!("dangling-decorator", result.errors[0].code);
+    assert_eq!("'@min' is not followed by a key to apply to.", result.errors[0].message);
+    assert_eq!(FilePosition { line: 2, column: 1 }, result.errors[0].position);
+    assert_eq!(7, result.errors[0].length);
+}
+
+#[test]
+fn invalid_key() {
+    let content = "\"PORT\"=3000\n=4000\nHOST=\"localhost\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    let messages: Vec<&str> = result.errors.iter().map(|e| e.message.as_str()).collect();
+
+    assert_eq!(
+        vec![
+            "'\"PORT\"' is not a valid key. Keys can't contain '\"'.",
+            "Expected a key before '='."
+        ],
+        messages
+    );
+
+    assert_eq!(1, result.keys.len());
+    assert_eq!("HOST", result.keys[0].key);
+}
+
+#[test]
+fn stray_bracket() {
+    let content = "PORT=3000]\nHOST=\"localhost\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!("unexpected-bracket", result.errors[0].code);
+    assert_eq!(FilePosition { line: 1, column: 10 }, result.errors[0].position);
+
+    assert_eq!(1, result.keys.len());
+    assert_eq!("HOST", result.keys[0].key);
+}
+
+#[test]
+fn line_without_equals() {
+    let content = "PORT 3000 # the port\nHOST=\"localhost\"";
+
+    let result = parse(content);
+
+    if result.valid {
+        panic!("Result should have been invalid.");
+    }
+
+    assert_eq!("missing-equals", result.errors[0].code);
+    assert_eq!("Expected '=' after 'PORT 3000'.", result.errors[0].message);
+    assert_eq!(9, result.errors[0].length);
+}
+
+#[test]
+fn reports_every_error() {
+    let content = "A=\"abc\n@min(1)\nB 1\nC=1]\n=2\nD=3\n@max(2)";
+
+    let result = parse(content);
+
+    let codes: Vec<&str> = result.errors.iter().map(|e| e.code.as_str()).collect();
+
+    assert_eq!(
+        vec![
+            "unterminated-string",
+            "missing-equals",
+            "unexpected-bracket",
+            "invalid-key",
+            "dangling-decorator"
+        ],
+        codes
+    );
+
+    assert_eq!(1, result.keys.len());
+    assert_eq!("D", result.keys[0].key);
+}
+
+#[test]
+fn whitespace_around_equals() {
+    let content = "PORT = 3000\n  HOST =\"localhost\"";
+
+    let result = parse(content);
+
+    assert!(result.valid);
+    assert_eq!(2, result.keys.len());
+
+    assert_eq!("PORT", result.keys[0].key);
+    assert_eq!(FilePosition { line: 1, column: 1 }, result.keys[0].position);
+    assert_eq!("3000", result.keys[0].value.to_string());
+
+    assert_eq!("HOST", result.keys[1].key);
+    assert_eq!(FilePosition { line: 2, column: 3 }, result.keys[1].position);
+}
+
+#[test]
+fn whitespace_isnt_part_of_invalid_keys() {
+    let content = "API URL = 1";
+
+    let result = parse(content);
+
+    assert!(!result.valid);
+    assert_eq!("'API URL' is not a valid key. Keys can only contain letters, digits and '_' and can't start with a digit.", result.keys[0].errors[0].message);
+    assert_eq!(FilePosition { line: 1, column: 1 }, result.keys[0].errors[0].position);
+    assert_eq!(7, result.keys[0].errors[0].length);
+}
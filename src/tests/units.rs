@@ -0,0 +1,59 @@
+use crate::parsing::{units::Unit, ValueType};
+
+#[test]
+fn parses_durations() {
+    assert_eq!(Ok(250.0), Unit::Duration.parse("250ms"));
+    assert_eq!(Ok(30_000.0), Unit::Duration.parse("30s"));
+    assert_eq!(Ok(300_000.0), Unit::Duration.parse("5m"));
+    assert_eq!(Ok(5_400_000.0), Unit::Duration.parse("1.5h"));
+    assert_eq!(Ok(86_400_000.0), Unit::Duration.parse("1d"));
+}
+
+#[test]
+fn parses_byte_sizes() {
+    assert_eq!(Ok(512.0), Unit::Bytes.parse("512B"));
+    assert_eq!(Ok(10_000_000.0), Unit::Bytes.parse("10MB"));
+    assert_eq!(Ok(10_485_760.0), Unit::Bytes.parse("10MiB"));
+    assert_eq!(Ok(1_073_741_824.0), Unit::Bytes.parse("1gib"));
+}
+
+#[test]
+fn parses_percentages() {
+    assert_eq!(Ok(0.05), Unit::Percent.parse("5%"));
+    assert_eq!(Ok(1.0), Unit::Percent.parse("100%"));
+}
+
+#[test]
+fn rejects_missing_or_unknown_units() {
+    let expected_error_message = "'30' is not a valid duration. Ex: `30s`";
+
+    assert_eq!(Err(expected_error_message.to_owned()), Unit::Duration.parse("30"));
+    assert!(Unit::Duration.parse("30 weeks").is_err());
+    assert!(Unit::Bytes.parse("-1MB").is_err());
+    assert!(Unit::Percent.parse("5").is_err());
+}
+
+#[test]
+fn normalizes_values() {
+    match Unit::Duration.normalize(&ValueType::String("30s".to_owned())) {
+        Some(ValueType::Integer(v)) => assert_eq!(30_000, v),
+        _ => panic!("Expected the duration to be normalized to an integer"),
+    }
+
+    match Unit::Percent.normalize(&ValueType::String("5%".to_owned())) {
+        Some(ValueType::Number(v)) => assert_eq!(0.05, v),
+        _ => panic!("Expected the percentage to be normalized to a number"),
+    }
+
+    match Unit::Bytes.normalize(&ValueType::StringArray(vec!["1KB".to_owned(), "1KiB".to_owned()])) {
+        Some(ValueType::IntegerArray(v)) => assert_eq!(vec![1000, 1024], v),
+        _ => panic!("Expected the byte sizes to be normalized to an integer array"),
+    }
+
+    match Unit::Percent.normalize(&ValueType::StringArray(vec!["5%".to_owned(), "100%".to_owned()])) {
+        Some(ValueType::NumberArray(v)) => assert_eq!(vec![0.05, 1.0], v),
+        _ => panic!("Expected the percentages to be normalized to a number array"),
+    }
+
+    assert!(Unit::Bytes.normalize(&ValueType::String("lots".to_owned())).is_none());
+}
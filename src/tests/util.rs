@@ -29,4 +29,35 @@ fn trims_quotes_returns_same() {
     let result = util::trim_quotes(input);
 
     assert_eq!(expected, result);
-}
\ No newline at end of file
+}
+#[test]
+fn edit_distance_counts_edits() {
+    assert_eq!(0, util::edit_distance("info", "info"));
+    assert_eq!(1, util::edit_distance("info", "inf"));
+    assert_eq!(2, util::edit_distance("debug", "dbeug"));
+}
+
+#[test]
+fn closest_match_ignores_distant_values() {
+    let options = vec![String::from("debug"), String::from("info")];
+
+    let result = util::closest_match("production", &options);
+
+    assert_eq!(None, result);
+}
+
+#[test]
+fn closest_match_prefers_swapped_characters() {
+    let options = vec![String::from("max"), String::from("min")];
+
+    assert_eq!(Some(String::from("min")), util::closest_match("mni", &options));
+}
+
+#[test]
+fn glob_match_backtracks() {
+    assert!(util::glob_match("*_URL", "PUBLIC_API_URL"));
+    assert!(util::glob_match("*", ""));
+    assert!(util::glob_match("A*B*C", "AxxBxxBxC"));
+    assert!(!util::glob_match("A*B*C", "AxxBxx"));
+    assert!(!util::glob_match("DB_?", "DB_"));
+}
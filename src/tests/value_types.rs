@@ -53,7 +53,7 @@ fn digits_string_correctly_parsed() {
 #[test]
 fn number_correctly_parsed() {
     let expected_key = "SOMETHING";
-    let expected_value: f64 = 25.0;
+    let expected_value: f64 = 25.5;
 
     let content = format!("{expected_key}={expected_value}");
 
@@ -75,6 +75,31 @@ fn number_correctly_parsed() {
     }
 }
 
+#[test]
+fn integer_correctly_parsed() {
+    let expected_key = "SOMETHING";
+    let expected_value: i64 = 9007199254740993;
+
+    let content = format!("{expected_key}={expected_value}");
+
+    let result = parse(&content);
+
+    for key in result.keys {
+        if key.key != expected_key {
+            panic!("'{}' was found. Expected '{expected_key}'", key.key);
+        } else {
+            match key.value {
+                crate::parsing::ValueType::Integer(v) => {
+                    if v != expected_value {
+                        panic!("'{v}' was found. Expected '{expected_value}'");
+                    }
+                }
+                _ => panic!("Invalid value type. Expected Integer"),
+            }
+        }
+    }
+}
+
 #[test]
 fn number_array_correctly_parsed() {
     let expected_key = "SOMETHING";
@@ -98,6 +123,33 @@ fn number_array_correctly_parsed() {
     }
 }
 
+#[test]
+fn integer_array_correctly_parsed() {
+    let expected_key = "SOMETHING";
+    let expected_value: Vec<i64> = vec![80, 443, 9007199254740993];
+
+    let content = format!("{expected_key}={:?}", expected_value);
+
+    let result = parse(&content);
+
+    for key in result.keys {
+        if key.key != expected_key {
+            panic!("'{}' was found. Expected '{expected_key}'", key.key);
+        } else {
+            match &key.value {
+                crate::parsing::ValueType::IntegerArray(v) => {
+                    assert_eq!(&expected_value, v);
+                }
+                _ => panic!("Invalid value type. Expected Integer Array"),
+            }
+
+            // Whole numbers are written back without a fraction
+            assert_eq!("[80, 443, 9007199254740993]", key.value.to_string());
+            assert_eq!("80,443,9007199254740993", key.value.env_value());
+        }
+    }
+}
+
 #[test]
 fn string_array_correctly_parsed() {
     let expected_key = "SOMETHING";
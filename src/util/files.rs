@@ -0,0 +1,82 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Returns the header written at the top of files generated from the source
+pub fn header(src: &str) -> String {
+    format!("# This file was generated from '{src}' by vnv.")
+}
+
+/// Returns true if the content starts with the header of a file generated by vnv
+///
+/// # Examples
+/// ```
+/// use vnv::util::files::is_generated;
+///
+/// assert!(is_generated("# This file was generated from '.vnv' by vnv.\n\nPORT=8080\n"));
+/// assert!(!is_generated("PORT=8080\n"));
+/// ```
+pub fn is_generated(content: &str) -> bool {
+    let first_line = content.lines().next().unwrap_or_default().trim_end();
+
+    first_line.starts_with("# This file was generated from '") && first_line.ends_with("' by vnv.")
+}
+
+/// Returns true if the file can be replaced by a build
+///
+/// Files that don't exist, are empty or start with the vnv header can be replaced.
+pub fn can_overwrite(path: &Path) -> bool {
+    match fs::read_to_string(path) {
+        Ok(content) => content.trim().is_empty() || is_generated(&content),
+        Err(err) => err.kind() == io::ErrorKind::NotFound,
+    }
+}
+
+/// Returns the path of the temporary file the contents are written to before replacing the file
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    path.with_file_name(format!(".{name}.{}.tmp", process::id()))
+}
+
+/// Writes the file through a temporary file that is renamed over it
+///
+/// The file is either fully written or left as it was if writing fails. Private files are only readable and
+/// writable by the owner (`0600`) on Unix, other files keep the permissions of the file they replace.
+pub fn write_atomic(path: &Path, contents: &str, private: bool) -> io::Result<()> {
+    let temp = temp_path(path);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+
+    // The mode is set when the file is created so the contents are never readable by others
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    let result = options.open(&temp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+
+        if !private {
+            if let Ok(metadata) = fs::metadata(path) {
+                file.set_permissions(metadata.permissions())?;
+            }
+        }
+
+        file.sync_all()
+    });
+
+    let result = result.and_then(|_| fs::rename(&temp, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+
+    result
+}
@@ -5,14 +5,21 @@ use std::{
     str::FromStr,
 };
 
+pub mod files;
+pub mod secrets;
+
+#[derive(Debug, PartialEq, Eq)]
 pub enum CompareResult {
     Less,
     Greater,
     Equal,
 }
 
-pub trait Compare {
-    fn cmp(&self, other: &f64) -> CompareResult;
+/// Compares numbers across integer and floating point types
+///
+/// Since `i64` implements `Ord` call this with `Compare::cmp(&a, &b)` to avoid ambiguity.
+pub trait Compare<Rhs = f64> {
+    fn cmp(&self, other: &Rhs) -> CompareResult;
 }
 
 impl Compare for f64 {
@@ -27,6 +34,37 @@ impl Compare for f64 {
     }
 }
 
+impl Compare<i64> for i64 {
+    fn cmp(&self, other: &i64) -> CompareResult {
+        match Ord::cmp(self, other) {
+            std::cmp::Ordering::Less => CompareResult::Less,
+            std::cmp::Ordering::Equal => CompareResult::Equal,
+            std::cmp::Ordering::Greater => CompareResult::Greater,
+        }
+    }
+}
+
+impl Compare<f64> for i64 {
+    fn cmp(&self, other: &f64) -> CompareResult {
+        // Whole numbers are compared as integers so that large values don't lose precision
+        if other.fract() == 0.0 && *other >= i64::MIN as f64 && *other <= i64::MAX as f64 {
+            return Compare::cmp(self, &(*other as i64));
+        }
+
+        Compare::cmp(&(*self as f64), other)
+    }
+}
+
+impl Compare<i64> for f64 {
+    fn cmp(&self, other: &i64) -> CompareResult {
+        match Compare::cmp(other, self) {
+            CompareResult::Less => CompareResult::Greater,
+            CompareResult::Greater => CompareResult::Less,
+            CompareResult::Equal => CompareResult::Equal,
+        }
+    }
+}
+
 /// Trims quotes around the passed string
 ///  
 /// # Examples
@@ -53,6 +91,124 @@ pub fn trim_quotes(val: &str) -> String {
     trimmed
 }
 
+/// Matches the text against a glob pattern where `*` matches any characters and `?` matches one
+///
+/// # Examples
+/// ```
+/// use vnv::util::glob_match;
+///
+/// assert!(glob_match("PUBLIC_*", "PUBLIC_API_URL"));
+/// assert!(glob_match("DB_?OST", "DB_HOST"));
+/// assert!(!glob_match("PUBLIC_*", "API_URL"));
+/// ```
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it was matched at, to backtrack to
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    while p < pattern.len() && pattern[p] == '*' {
+        p += 1;
+    }
+
+    p == pattern.len()
+}
+
+/// Calculates the number of single character edits needed to turn one string into another
+///
+/// # Examples
+/// ```
+/// let distance = vnv::util::edit_distance("warm", "warn");
+///
+/// assert_eq!(distance, 1);
+/// ```
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + if a_char == *b_char { 0 } else { 1 };
+            let insertion = current[j] + 1;
+            let deletion = previous[j + 1] + 1;
+
+            current.push(substitution.min(insertion).min(deletion));
+        }
+
+        previous = current;
+    }
+
+    previous[b_chars.len()]
+}
+
+/// Finds the option that is closest to the value if it is close enough to be a likely typo
+///
+/// # Examples
+/// ```
+/// let options = vec![String::from("debug"), String::from("info")];
+///
+/// let result = vnv::util::closest_match("inf", &options);
+///
+/// assert_eq!(result, Some(String::from("info")));
+/// ```
+pub fn closest_match(value: &str, options: &[String]) -> Option<String> {
+    let max_distance = (value.chars().count() / 3).max(2);
+    let value = value.to_lowercase();
+
+    // Options at the same distance are ranked by the characters they share with the value
+    // so that swapped characters (`mni`) are matched to the right option (`min`)
+    let shared = |option: &str| {
+        let mut remaining: Vec<char> = value.chars().collect();
+
+        option
+            .chars()
+            .filter(|c| match remaining.iter().position(|r| r == c) {
+                Some(i) => {
+                    remaining.remove(i);
+                    true
+                }
+                None => false,
+            })
+            .count()
+    };
+
+    options
+        .iter()
+        .map(|option| {
+            let option_lower = option.to_lowercase();
+
+            (edit_distance(&value, &option_lower), shared(&option_lower), option)
+        })
+        .filter(|(distance, _, _)| *distance <= max_distance)
+        .min_by(|(a, a_shared, a_option), (b, b_shared, b_option)| {
+            a.cmp(b)
+                .then(b_shared.cmp(a_shared))
+                .then(a_option.cmp(b_option))
+        })
+        .map(|(_, _, option)| option.to_owned())
+}
+
 /// Adds whitespace to the left of the number so that it meets the min_length provided
 ///
 /// # Returns
@@ -66,16 +222,17 @@ pub fn trim_quotes(val: &str) -> String {
 /// let result = vnv::util::number_pad(number, 3);
 ///
 /// assert_eq!(result, "  1");
+/// assert_eq!(vnv::util::number_pad(1000, 3), "1000");
 /// ```
 pub fn number_pad(num: u32, min_length: usize) -> String {
     let num_str = num.to_string();
 
-    let padding = min_length - num_str.len();
+    let padding = min_length.saturating_sub(num_str.len());
 
     let mut result = String::new();
 
     for _ in 0..padding {
-        result.push_str(" ");
+        result.push(' ');
     }
 
     result.push_str(&num_str);
@@ -116,11 +273,11 @@ pub enum Answer {
     No,
 }
 
-impl Answer {
-    pub fn to_string(&self) -> String {
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Answer::Yes => String::from("y"),
-            Answer::No => String::from("N"),
+            Answer::Yes => write!(f, "y"),
+            Answer::No => write!(f, "N"),
         }
     }
 }
@@ -0,0 +1,227 @@
+mod common;
+
+use common::{project, stderr, stdout, vnv, vnv_with_input};
+use std::fs;
+
+#[test]
+fn check_passes_when_up_to_date() {
+    let dir = project("up-to-date", "PORT=8080\nHOST=\"localhost\"\n", "{}");
+
+    assert!(vnv(&dir, &["build"]).status.success());
+
+    let output = vnv(&dir, &["build", "--check"]);
+
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("'.env' is up to date."));
+}
+
+#[test]
+fn check_reports_drift() {
+    let dir = project("drift", "PORT=8080\nHOST=\"localhost\"\nDEBUG=\"false\"\n", "{}");
+
+    assert!(vnv(&dir, &["build"]).status.success());
+
+    fs::write(
+        dir.join(".env"),
+        "PORT=3000\nHOST=\"localhost\"\nEXTRA=\"hand-written\"\n",
+    )
+    .unwrap();
+
+    let output = vnv(&dir, &["build", "--check"]);
+    let out = stdout(&output);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(out.contains("PORT ~ expected 8080 found 3000"));
+    assert!(out.contains("DEBUG - removed from .env"));
+    assert!(out.contains("EXTRA + added to .env"));
+    assert!(out.contains("1 added, 1 removed, 1 changed"));
+}
+
+#[test]
+fn check_fails_without_output() {
+    let dir = project("missing", "PORT=8080\n", "{}");
+
+    let output = vnv(&dir, &["build", "--check"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("'.env' does not exist."));
+}
+
+#[test]
+fn check_fails_on_unreadable_json() {
+    let dir = project(
+        "unreadable",
+        "PORT=8080\n",
+        r#"{"build":{"targets":[{"name":"web","output":"env.json","format":"json"}]}}"#,
+    );
+
+    fs::write(dir.join("env.json"), "{\"PORT\": 8080").unwrap();
+
+    let output = vnv(&dir, &["build", "--check"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("'env.json' can't be read:"));
+}
+
+#[test]
+fn check_compares_json() {
+    let dir = project(
+        "json",
+        "PORT=8080\nHOSTS=[\"a\", \"b\"]\n",
+        r#"{"build":{"targets":[{"name":"web","output":"env.json","format":"json"}]}}"#,
+    );
+
+    assert!(vnv(&dir, &["build"]).status.success());
+    assert!(vnv(&dir, &["build", "--check"]).status.success());
+
+    fs::write(dir.join("env.json"), "{\"PORT\": 80, \"HOSTS\": [\"a\", \"b\"]}").unwrap();
+
+    let output = vnv(&dir, &["build", "--check"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("PORT ~ expected 8080 found 80"));
+}
+
+#[test]
+fn check_masks_cloaked_values() {
+    let dir = project("cloaked", "@private\nSECRET=\"hunter22\"\n@public\nPORT=8080\n", "{}");
+
+    assert!(vnv(&dir, &["build"]).status.success());
+
+    fs::write(dir.join(".env"), "SECRET=\"swordfish\"\nPORT=3000\n").unwrap();
+
+    let output = vnv(&dir, &["build", "--check", "--cloak=private"]);
+    let out = stdout(&output);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(out.contains("SECRET ~ expected ********** found ***********"));
+    assert!(!out.contains("hunter22") && !out.contains("swordfish"));
+    assert!(out.contains("PORT ~ expected 8080 found 3000"));
+}
+
+#[test]
+fn check_accepts_previous_names() {
+    let dir = project("renamed", "@alias(\"DB_URL\")\nDATABASE_URL=\"postgres://localhost\"\n", "{}");
+
+    fs::write(dir.join(".env"), "DB_URL=\"postgres://localhost\"\n").unwrap();
+
+    let output = vnv(&dir, &["build", "--check"]);
+    let out = stdout(&output);
+
+    assert!(output.status.success());
+    assert!(out.contains("DB_URL ! uses the previous name of DATABASE_URL."));
+    assert!(out.contains("'.env' is up to date."));
+}
+
+#[test]
+fn builds_integer_arrays() {
+    let dir = project(
+        "integer-arrays",
+        "PORTS=[80, 443]\nIDS=[9007199254740993]\n",
+        r#"{"build":{"minify":true,"targets":[{"name":"env","output":".env"},{"name":"web","output":"env.json","format":"json"}]}}"#,
+    );
+
+    assert!(vnv(&dir, &["build"]).status.success());
+
+    assert_eq!(
+        "PORTS=[80, 443]\nIDS=[9007199254740993]\n",
+        fs::read_to_string(dir.join(".env")).unwrap()
+    );
+    assert_eq!(
+        "{\"IDS\":[9007199254740993],\"PORTS\":[80,443]}\n",
+        fs::read_to_string(dir.join("env.json")).unwrap()
+    );
+}
+
+#[test]
+fn normalize_is_a_shorthand_for_the_override() {
+    let dir = project("normalize", "@duration\nTIMEOUT=30s\n", r#"{"build":{"minify":true}}"#);
+
+    assert!(vnv(&dir, &["build", "--normalize"]).status.success());
+    assert_eq!("TIMEOUT=30000\n", fs::read_to_string(dir.join(".env")).unwrap());
+
+    // The explicit override wins over the shorthand
+    assert!(vnv(&dir, &["build", "--normalize", "--build-normalize=false"]).status.success());
+    assert_eq!("TIMEOUT=\"30s\"\n", fs::read_to_string(dir.join(".env")).unwrap());
+}
+
+#[test]
+fn reads_the_source_from_stdin() {
+    let dir = project("stdin", "", r#"{"build":{"minify":true}}"#);
+
+    let output = vnv_with_input(&dir, &["build", "--src", "-"], "PORT=8080\n");
+
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Checking '<stdin>'..."));
+    assert_eq!("PORT=8080\n", fs::read_to_string(dir.join(".env")).unwrap());
+
+    let output = vnv_with_input(&dir, &["check", "--file", "-"], "@min(100)\nPORT=80\n");
+
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("--> <stdin>:2:6"));
+}
+
+#[test]
+fn writes_only_the_build_to_stdout() {
+    let dir = project("stdout", "PORT=8080\n", r#"{"build":{"minify":true}}"#);
+
+    let output = vnv(&dir, &["build", "--stdout", "--dev", "--prod"]);
+
+    assert!(output.status.success());
+    assert_eq!("PORT=8080\n", stdout(&output));
+    assert!(stderr(&output).contains("Checking '.vnv'..."));
+    assert!(stderr(&output).contains("WARN: You provided multiple environment flags"));
+    assert!(!dir.join(".env").exists());
+}
+
+#[test]
+fn stdout_builds_one_target() {
+    let dir = project(
+        "stdout-targets",
+        "PORT=8080\n",
+        r#"{"build":{"targets":[{"name":"web","output":"web.env"},{"name":"api","output":"api.env","format":"json"}]}}"#,
+    );
+
+    let output = vnv(&dir, &["build", "--stdout"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("--stdout can only write one build target. Pick one with --target."));
+
+    let output = vnv(&dir, &["build", "--stdout", "--target", "api"]);
+
+    assert!(output.status.success());
+    assert_eq!("{\n  \"PORT\": 8080\n}\n", stdout(&output));
+}
+
+#[test]
+fn protect_errors_go_to_stderr() {
+    let dir = project("protect", "PORT=8080\n", r#"{"build":{"protect":true}}"#);
+
+    fs::write(dir.join(".env"), "SECRET=hand-written\n").unwrap();
+
+    let output = vnv(&dir, &["build"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("'.env' wasn't generated by vnv so it won't be overwritten."));
+    assert_eq!("SECRET=hand-written\n", fs::read_to_string(dir.join(".env")).unwrap());
+}
+
+#[test]
+fn stdout_only_carries_the_build() {
+    let dir = project("stdout-clean", "@max(1)\nR=[0.5, 2.5]\n", r#"{"build":{"minify":true}}"#);
+
+    // The failed check is only written to stderr
+    let output = vnv(&dir, &["build", "--stdout"]);
+
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("2.5 is too large. Maximum value is 1."));
+
+    // Config errors go to stderr so they never end up in the piped build
+    let output = vnv(&dir, &["build", "--stdout", "--cloak-reveal=many"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("ERROR:"));
+}
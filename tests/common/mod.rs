@@ -0,0 +1,62 @@
+// Each test binary only uses some of the helpers
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+/// Creates a directory for the test in the temp directory with the source and config written to it
+pub fn project(name: &str, source: &str, config: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vnv-cli-{name}-{}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(dir.join(".vnv"), source).unwrap();
+    fs::write(dir.join(".vnv.config.json"), config).unwrap();
+
+    dir
+}
+
+/// Creates the command to run vnv in the directory without any VNV_* variables from the environment
+fn command(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_vnv"));
+
+    for (name, _) in std::env::vars().filter(|(name, _)| name.starts_with("VNV_")) {
+        command.env_remove(name);
+    }
+
+    command.current_dir(dir).args(args).env("NO_COLOR", "1");
+
+    command
+}
+
+/// Runs vnv in the directory
+pub fn vnv(dir: &Path, args: &[&str]) -> Output {
+    command(dir, args).output().unwrap()
+}
+
+/// Runs vnv in the directory with the input written to stdin
+pub fn vnv_with_input(dir: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = command(dir, args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,20 @@
+mod common;
+
+use common::{project, stdout, vnv};
+
+#[test]
+fn escapes_markdown_keys() {
+    let dir = project(
+        "docs-escape",
+        "A|B=1\n@matches(\"a`b|c\")\nC`D=\"x\"\n",
+        "{}",
+    );
+
+    let output = vnv(&dir, &["docs"]);
+    let out = stdout(&output);
+
+    assert!(output.status.success());
+    assert!(out.contains("| `A\\|B` | private |"));
+    assert!(out.contains("| `` C`D `` | private |"));
+    assert!(out.contains("| `` @matches(\"a`b\\|c\") `` |"));
+}